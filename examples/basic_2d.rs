@@ -5,9 +5,10 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(CellsPlugin)
+        .add_plugins(CellTransformPlugin::<GameLayer>::default())
+        .insert_resource(GridGeometry::<GameLayer>::new([16.0, 16.0]))
         .add_systems(Startup, spawn)
         .add_systems(Update, move_character)
-        .add_systems(PostUpdate, sync_cell_transforms)
         .run();
 }
 
@@ -95,12 +96,3 @@ fn move_character(
         cell_commands.move_cell(**char_c, new_coord);
     }
 }
-
-fn sync_cell_transforms(
-    mut cells: CellQuery<GameLayer, (&CellCoord, &mut Transform), Changed<CellCoord>>,
-) {
-    for (cell_c, mut transform) in cells.iter_mut() {
-        transform.translation.x = cell_c[0] as f32 * 16.0;
-        transform.translation.y = cell_c[1] as f32 * 16.0;
-    }
-}
@@ -7,9 +7,9 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(CellsPlugin)
+        .add_plugins(CellTransformPlugin::<GameLayer, 3>::default())
         .add_systems(Startup, spawn)
         .add_systems(Update, move_character)
-        .add_systems(PostUpdate, sync_cell_transforms)
         .run();
 }
 
@@ -153,14 +153,3 @@ fn move_character(
         cell_commands.move_cell(**char_c, new_coord);
     }
 }
-
-fn sync_cell_transforms(
-    // Important, you have to put the 3 in all these places at the moment!!!
-    mut cells: CellQuery<GameLayer, (&CellCoord<3>, &mut Transform), Changed<CellCoord<3>>, 3>,
-) {
-    for (cell_c, mut transform) in cells.iter_mut() {
-        transform.translation.x = cell_c[0] as f32;
-        transform.translation.y = cell_c[1] as f32;
-        transform.translation.z = cell_c[2] as f32;
-    }
-}
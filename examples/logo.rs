@@ -5,8 +5,9 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(CellsPlugin)
+        .add_plugins(CellTransformPlugin::<GameLayer>::default())
+        .insert_resource(GridGeometry::<GameLayer>::new([16.0, 16.0]))
         .add_systems(Startup, spawn)
-        .add_systems(Update, sync_cell_transforms)
         .run();
 }
 
@@ -55,12 +56,3 @@ eeeee  eeee e    e e    e       eeee eeee e     e     eeeee
         (Block, sprite_bundle.clone())
     });
 }
-
-fn sync_cell_transforms(
-    mut cells: CellQuery<GameLayer, (&CellCoord, &mut Transform), Changed<CellCoord>>,
-) {
-    for (cell_c, mut transform) in cells.iter_mut() {
-        transform.translation.x = cell_c[0] as f32 * 16.0;
-        transform.translation.y = cell_c[1] as f32 * 16.0;
-    }
-}
@@ -13,11 +13,13 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(CellsPlugin)
+        .add_plugins(CellTransformPlugin::<GameLayer>::default())
+        .add_plugins(CellCleanupPlugin::<GameLayer>::default())
+        .insert_resource(GridGeometry::<GameLayer>::new([16.0, 16.0]))
         .add_plugins(LogDiagnosticsPlugin::default())
         .add_plugins(FrameTimeDiagnosticsPlugin)
         .add_systems(Startup, spawn)
         .add_systems(Update, (add_damage, check_damage).chain())
-        .add_systems(PostUpdate, sync_cell_transforms)
         .run();
 }
 
@@ -75,14 +77,14 @@ fn add_damage(
     windows: Query<&Window, With<PrimaryWindow>>,
     camera: Query<(&Camera, &GlobalTransform)>,
     buttons: Res<Input<MouseButton>>,
+    geometry: Res<GridGeometry<GameLayer>>,
 ) {
     let (cam, cam_t) = camera.single();
     let cursor_pos = windows
         .single()
         .cursor_position()
         .and_then(|cursor| cam.viewport_to_world(cam_t, cursor.xy()))
-        .map(|ray| ray.origin.truncate())
-        .map(|pos| world_to_cell(pos.into(), 16.0));
+        .map(|ray| geometry.world_to_cell(ray.origin));
 
     if let Some(damage_pos) = buttons
         .just_pressed(MouseButton::Left)
@@ -104,7 +106,7 @@ fn add_damage(
         .then_some(cursor_pos)
         .flatten()
     {
-        let chunk_c = calculate_chunk_coordinate(damage_pos, GameLayer::CHUNK_SIZE);
+        let chunk_c = calculate_chunk_coordinate(damage_pos, GameLayer::chunk_size());
         commands.cells::<GameLayer, 2>().despawn_chunk(chunk_c);
     }
 }
@@ -128,12 +130,3 @@ fn check_damage(
         }
     }
 }
-
-fn sync_cell_transforms(
-    mut cells: CellQuery<GameLayer, (&CellCoord, &mut Transform), Changed<CellCoord>>,
-) {
-    for (cell_c, mut transform) in cells.iter_mut() {
-        transform.translation.x = cell_c[0] as f32 * 16.0;
-        transform.translation.y = cell_c[1] as f32 * 16.0;
-    }
-}
@@ -3,10 +3,21 @@ use bevy::prelude::*;
 use std::collections::HashMap;
 use std::ops::Deref;
 
+pub mod automaton;
 pub mod cell_query;
 pub mod chunk_query;
+pub mod cleanup;
 pub mod commands;
 pub mod coords;
+pub mod data;
+pub mod error;
+pub mod events;
+pub mod fov;
+pub mod generation;
+pub mod pathfinding;
+pub mod save;
+pub mod streaming;
+pub mod transform;
 
 // ===============
 // Cell Components
@@ -95,7 +106,64 @@ pub struct InMap<L, const N: usize>(std::marker::PhantomData<L>);
 /// Adds type level info on how a Cell Map should be treated.
 pub trait CellMapLabel: Send + Sync {
     /// How many cells per dimension a chunk in this map extends.
+    /// # Note
+    /// This and the other consts are the defaults for maps, see [CellMapConfig] to pick them at runtime.
     const CHUNK_SIZE: usize;
+    /// How many cells a chunk extends along each axis, for chunks that aren't cubes.
+    /// Axes left out use [CHUNK_SIZE](CellMapLabel::CHUNK_SIZE), so `&[16, 16, 256]` gives tall voxel chunks.
+    /// # Note
+    /// Giving more axes than the map has panics when the map is created.
+    const CHUNK_EXTENT: &'static [usize] = &[];
+    /// What happens when a cell is inserted into an occupied coordinate.
+    const INSERT_POLICY: InsertPolicy = InsertPolicy::ReplaceDespawn;
+    /// Whether a chunk is despawned and removed from the map once its last cell is taken out.
+    /// # Note
+    /// Chunks carrying [CellData](data::CellData) layers are kept, see [HasCellData](data::HasCellData).
+    const DESPAWN_EMPTY_CHUNKS: bool = false;
+
+    /// Gets how many cells a chunk extends along each axis.
+    #[inline]
+    fn chunk_size<const N: usize>() -> [usize; N] {
+        assert!(
+            Self::CHUNK_EXTENT.len() <= N,
+            "CHUNK_EXTENT has {} axes, but the map only has {N}",
+            Self::CHUNK_EXTENT.len()
+        );
+        std::array::from_fn(|i| {
+            Self::CHUNK_EXTENT
+                .get(i)
+                .copied()
+                .unwrap_or(Self::CHUNK_SIZE)
+        })
+    }
+}
+
+/// What happens when a cell is inserted into a coordinate that already has a cell.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum InsertPolicy {
+    /// The old cell is despawned and replaced.
+    #[default]
+    ReplaceDespawn,
+    /// The old cell is removed from the map and replaced, but not despawned.
+    ReplaceReturn,
+    /// The old cell is kept and the new cell is despawned.
+    Reject,
+    /// The old cell is kept and an error is returned, the new cell is left as is.
+    Error,
+}
+
+/// What happens when a cell is spawned or moved to a coordinate outside the bounds of a map.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum BoundsPolicy {
+    /// The cell isn't placed and [CellError::OutOfBounds](error::CellError::OutOfBounds) is returned.
+    #[default]
+    Reject,
+    /// The coordinate is clamped to the nearest cell inside the bounds.
+    Clamp,
 }
 
 #[derive(Component)]
@@ -104,6 +172,7 @@ where
     L: CellMapLabel + 'static,
 {
     pub(crate) chunks: HashMap<ChunkCoord<N>, Entity>,
+    pub(crate) config: CellMapConfig<L, N>,
     label: std::marker::PhantomData<L>,
 }
 
@@ -112,9 +181,153 @@ where
     L: CellMapLabel + 'static,
 {
     fn default() -> Self {
+        Self::new(CellMapConfig::default())
+    }
+}
+
+impl<L, const N: usize> CellMap<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    /// Creates an empty map with the given settings.
+    /// # Note
+    /// Spawn this on an entity before using it with [cells_on](commands::CellCommandExt::cells_on),
+    /// otherwise maps are created with the settings from the label.
+    pub fn new(config: CellMapConfig<L, N>) -> Self {
         Self {
             chunks: Default::default(),
+            config,
+            label: Default::default(),
+        }
+    }
+
+    /// Gets the settings the map was created with.
+    pub fn config(&self) -> &CellMapConfig<L, N> {
+        &self.config
+    }
+}
+
+/// Settings for a cell map chosen at runtime, for example from data files.
+/// # Note
+/// Defaults to the consts on the label, and maps are unbounded unless given bounds.
+/// The settings can't change once the map is created.
+pub struct CellMapConfig<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    chunk_size: [usize; N],
+    insert_policy: InsertPolicy,
+    bounds: Option<([isize; N], [isize; N])>,
+    bounds_policy: BoundsPolicy,
+    label: std::marker::PhantomData<L>,
+}
+
+impl<L, const N: usize> Default for CellMapConfig<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    fn default() -> Self {
+        Self {
+            chunk_size: L::chunk_size(),
+            insert_policy: L::INSERT_POLICY,
+            bounds: None,
+            bounds_policy: BoundsPolicy::default(),
             label: Default::default(),
         }
     }
 }
+
+impl<L, const N: usize> Clone for CellMapConfig<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L, const N: usize> Copy for CellMapConfig<L, N> where L: CellMapLabel + 'static {}
+
+impl<L, const N: usize> CellMapConfig<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    /// Sets how many cells a chunk extends along each axis.
+    /// # Panics
+    /// Panics if any axis is zero.
+    pub fn with_chunk_size(mut self, chunk_size: [usize; N]) -> Self {
+        assert!(
+            chunk_size.iter().all(|size| *size > 0),
+            "chunk size {chunk_size:?} must be at least 1 along every axis"
+        );
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Sets what happens when a cell is inserted into an occupied coordinate.
+    pub fn with_insert_policy(mut self, insert_policy: InsertPolicy) -> Self {
+        self.insert_policy = insert_policy;
+        self
+    }
+
+    /// Limits the map to the cells between `corner_1` and `corner_2`, inclusive.
+    pub fn with_bounds(mut self, corner_1: [isize; N], corner_2: [isize; N]) -> Self {
+        let min = std::array::from_fn(|i| corner_1[i].min(corner_2[i]));
+        let max = std::array::from_fn(|i| corner_1[i].max(corner_2[i]));
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// Sets what happens when a cell is spawned or moved outside the bounds of the map.
+    pub fn with_bounds_policy(mut self, bounds_policy: BoundsPolicy) -> Self {
+        self.bounds_policy = bounds_policy;
+        self
+    }
+
+    /// Gets how many cells a chunk extends along each axis.
+    #[inline]
+    pub fn chunk_size(&self) -> [usize; N] {
+        self.chunk_size
+    }
+
+    /// Gets what happens when a cell is inserted into an occupied coordinate.
+    #[inline]
+    pub fn insert_policy(&self) -> InsertPolicy {
+        self.insert_policy
+    }
+
+    /// Gets the lowest and highest cell in the map, or `None` if the map is unbounded.
+    #[inline]
+    pub fn bounds(&self) -> Option<([isize; N], [isize; N])> {
+        self.bounds
+    }
+
+    /// Gets what happens when a cell is spawned or moved outside the bounds of the map.
+    #[inline]
+    pub fn bounds_policy(&self) -> BoundsPolicy {
+        self.bounds_policy
+    }
+
+    /// Returns true if the coordinate is inside the bounds of the map.
+    #[inline]
+    pub fn contains(&self, cell_c: [isize; N]) -> bool {
+        match self.bounds {
+            Some((min, max)) => (0..N).all(|i| (min[i]..=max[i]).contains(&cell_c[i])),
+            None => true,
+        }
+    }
+
+    /// Gets where a cell headed for a coordinate ends up, following the [BoundsPolicy] of the map.
+    #[inline]
+    pub(crate) fn resolve(&self, cell_c: [isize; N]) -> Result<[isize; N], error::CellError<N>> {
+        match (self.bounds, self.bounds_policy) {
+            (Some(_), BoundsPolicy::Reject) if !self.contains(cell_c) => {
+                Err(error::CellError::OutOfBounds { cell_c })
+            }
+            (Some((min, max)), BoundsPolicy::Clamp) => {
+                Ok(std::array::from_fn(|i| cell_c[i].clamp(min[i], max[i])))
+            }
+            _ => Ok(cell_c),
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+};
+
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::With,
+        system::{CommandQueue, Commands, Resource},
+        world::{Mut, World},
+    },
+};
+
+use super::{
+    commands::CellCommandExt,
+    coords::{neighbors, Adjacency},
+    CellCoord, CellMap, CellMapLabel, Chunk,
+};
+
+/// A rule that decides the next state of each cell in a [CellularAutomaton].
+/// # Note
+/// The rule is a resource, insert it into the world along with a [CellularAutomatonPlugin].
+pub trait AutomatonRule<L, const N: usize = 2>: Resource
+where
+    L: CellMapLabel + 'static,
+{
+    /// The per-cell state the rule reads and writes, cells without it are ignored
+    /// and never overwritten by a spawn.
+    type State: Component + Clone + PartialEq;
+
+    /// Which cells are considered neighbors, empty neighbors of a cell are also evaluated
+    /// so the rule can spawn cells into them. Neighbors occupied by a cell without a state,
+    /// or outside the bounds of the map, are skipped.
+    fn adjacency(&self) -> Adjacency {
+        Adjacency::MOORE
+    }
+
+    /// Calculates the next state of a cell from the previous generation.
+    /// Returning `None` despawns the cell, returning a state for an empty cell spawns one.
+    fn step(
+        &self,
+        state: Option<&Self::State>,
+        neighborhood: &Neighborhood<Self::State, N>,
+    ) -> Option<Self::State>;
+}
+
+/// Steps the [CellularAutomaton] for the label with the rule `R` once every update.
+/// # Note
+/// To step at a different rate, add [step_automaton] to another schedule
+/// and initialize the [CellularAutomaton] resource instead.
+pub struct CellularAutomatonPlugin<L, R, const N: usize = 2>(PhantomData<(L, R)>);
+
+impl<L, R, const N: usize> Default for CellularAutomatonPlugin<L, R, N> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<L, R, const N: usize> Plugin for CellularAutomatonPlugin<L, R, N>
+where
+    L: CellMapLabel + Send + 'static,
+    R: AutomatonRule<L, N>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CellularAutomaton<L, R, N>>()
+            .add_systems(Update, step_automaton::<L, R, N>);
+    }
+}
+
+/// Runs the rule `R` over every cell map for the label.
+/// # Note
+/// Each step reads the states of every cell into a front buffer chunk by chunk, evaluates the
+/// rule into a back buffer, then applies the changes, so the rule only ever sees the previous generation.
+#[derive(Resource)]
+pub struct CellularAutomaton<L, R, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+    R: AutomatonRule<L, N>,
+{
+    /// Stops the automaton from stepping while true.
+    pub paused: bool,
+    generation: u64,
+    front: HashMap<[isize; N], (Entity, R::State)>,
+    back: Vec<([isize; N], Option<R::State>)>,
+    stateless: HashSet<[isize; N]>,
+    label: PhantomData<L>,
+}
+
+impl<L, R, const N: usize> Default for CellularAutomaton<L, R, N>
+where
+    L: CellMapLabel + 'static,
+    R: AutomatonRule<L, N>,
+{
+    fn default() -> Self {
+        Self {
+            paused: false,
+            generation: 0,
+            front: HashMap::new(),
+            back: Vec::new(),
+            stateless: HashSet::new(),
+            label: PhantomData,
+        }
+    }
+}
+
+impl<L, R, const N: usize> CellularAutomaton<L, R, N>
+where
+    L: CellMapLabel + Send + 'static,
+    R: AutomatonRule<L, N>,
+{
+    /// Gets the number of steps the automaton has run.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn step_map(&mut self, world: &mut World, rule: &R, map_id: Entity) {
+        let Self {
+            front,
+            back,
+            stateless,
+            ..
+        } = self;
+        front.clear();
+        back.clear();
+        stateless.clear();
+
+        // Read the previous generation into the front buffer chunk by chunk
+        let Some(map) = world.get::<CellMap<L, N>>(map_id) else {
+            return;
+        };
+        let config = map.config;
+        for chunk_id in map.chunks.values() {
+            let Some(chunk) = world.get::<Chunk>(*chunk_id) else {
+                continue;
+            };
+            for cell_id in chunk.cells.iter().flatten() {
+                let Some(cell_e) = world.get_entity(*cell_id) else {
+                    continue;
+                };
+                let Some(cell_c) = cell_e.get::<CellCoord<N>>() else {
+                    continue;
+                };
+                match cell_e.get::<R::State>() {
+                    Some(state) => {
+                        front.insert(**cell_c, (*cell_id, state.clone()));
+                    }
+                    None => {
+                        stateless.insert(**cell_c);
+                    }
+                }
+            }
+        }
+
+        // Evaluate every cell with a state, and the empty cells around them, into the back buffer.
+        // Cells without a state aren't the rule's to replace, and cells outside the bounds of the map
+        // would be clamped onto the cells at its edge
+        let adjacency = rule.adjacency();
+        let mut visited = HashSet::new();
+        for cell_c in front.keys() {
+            for cell_c in std::iter::once(*cell_c).chain(neighbors(*cell_c, adjacency)) {
+                if !visited.insert(cell_c)
+                    || stateless.contains(&cell_c)
+                    || !config.contains(cell_c)
+                {
+                    continue;
+                }
+                let state = front.get(&cell_c).map(|(_, state)| state);
+                let neighborhood = Neighborhood {
+                    cell_c,
+                    adjacency,
+                    states: front,
+                };
+                let next = rule.step(state, &neighborhood);
+                if state != next.as_ref() {
+                    back.push((cell_c, next));
+                }
+            }
+        }
+
+        // Apply the changes, spawning and despawning cells in a single batch
+        let mut spawned = HashMap::new();
+        let mut despawned = Vec::new();
+        for (cell_c, next) in back.drain(..) {
+            match (front.get(&cell_c), next) {
+                (Some((cell_id, _)), Some(next)) => {
+                    *world.get_mut::<R::State>(*cell_id).unwrap() = next;
+                }
+                (Some(_), None) => despawned.push(cell_c),
+                (None, Some(next)) => {
+                    spawned.insert(cell_c, next);
+                }
+                (None, None) => {}
+            }
+        }
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        let mut cells = commands.cells_on::<L, N>(map_id);
+        cells.despawn_cell_batch(despawned);
+        cells.spawn_cell_batch(spawned.keys().copied().collect::<Vec<_>>(), move |cell_c| {
+            spawned[&cell_c].clone()
+        });
+        queue.apply(world);
+    }
+}
+
+/// The cells around a cell being evaluated by an [AutomatonRule], as of the previous generation.
+pub struct Neighborhood<'a, S, const N: usize = 2> {
+    cell_c: [isize; N],
+    adjacency: Adjacency,
+    states: &'a HashMap<[isize; N], (Entity, S)>,
+}
+
+impl<'a, S, const N: usize> Neighborhood<'a, S, N> {
+    /// Gets the coordinate of the cell being evaluated.
+    pub fn cell_c(&self) -> [isize; N] {
+        self.cell_c
+    }
+
+    /// Gets the state of any cell in the map.
+    pub fn get(&self, cell_c: [isize; N]) -> Option<&'a S> {
+        self.states.get(&cell_c).map(|(_, state)| state)
+    }
+
+    /// Gets the state of the cell at an offset from the cell being evaluated.
+    pub fn get_offset(&self, offset: [isize; N]) -> Option<&'a S> {
+        let mut cell_c = self.cell_c;
+        for (c, o) in cell_c.iter_mut().zip(offset) {
+            *c += o;
+        }
+        self.get(cell_c)
+    }
+
+    /// Iterate over the neighbors that have a state, along with their coordinates.
+    pub fn neighbors(&self) -> impl Iterator<Item = ([isize; N], &'a S)> + '_ {
+        neighbors(self.cell_c, self.adjacency)
+            .filter_map(|cell_c| Some((cell_c, self.get(cell_c)?)))
+    }
+
+    /// Counts the neighbors that have a state.
+    pub fn count(&self) -> usize {
+        self.neighbors().count()
+    }
+}
+
+/// Steps the [CellularAutomaton] for the label with the rule `R` once, if it isn't paused.
+pub fn step_automaton<L, R, const N: usize>(world: &mut World)
+where
+    L: CellMapLabel + Send + 'static,
+    R: AutomatonRule<L, N>,
+{
+    world.resource_scope(|world, mut automaton: Mut<CellularAutomaton<L, R, N>>| {
+        if automaton.paused {
+            return;
+        }
+        world.resource_scope(|world, rule: Mut<R>| {
+            let maps = world
+                .query_filtered::<Entity, With<CellMap<L, N>>>()
+                .iter(world)
+                .collect::<Vec<_>>();
+            for map_id in maps {
+                automaton.step_map(world, &rule, map_id);
+            }
+        });
+        automaton.generation += 1;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cells::{commands::CellWorldExt, BoundsPolicy, CellMapConfig},
+        CellsPlugin,
+    };
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 4;
+    }
+
+    #[derive(Component, Clone, PartialEq)]
+    struct Alive;
+
+    #[derive(Resource)]
+    struct Life;
+
+    impl AutomatonRule<TestLayer> for Life {
+        type State = Alive;
+
+        fn step(&self, state: Option<&Alive>, neighborhood: &Neighborhood<Alive>) -> Option<Alive> {
+            match (state, neighborhood.count()) {
+                (Some(_), 2 | 3) | (None, 3) => Some(Alive),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn blinker() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellsPlugin,
+            CellularAutomatonPlugin::<TestLayer, Life>::default(),
+        ))
+        .insert_resource(Life);
+
+        // Straddle a chunk border so spawns and despawns touch more than one chunk
+        let mut cells = app.world.cells::<TestLayer, 2>();
+        for cell_c in [[0, -1], [0, 0], [0, 1]] {
+            cells.spawn_cell(cell_c, Alive).unwrap();
+        }
+
+        let alive = |world: &mut World| {
+            let mut cells = world
+                .query_filtered::<&CellCoord, With<Alive>>()
+                .iter(world)
+                .map(|cell_c| **cell_c)
+                .collect::<Vec<_>>();
+            cells.sort();
+            cells
+        };
+
+        app.update();
+        assert_eq!(alive(&mut app.world), vec![[-1, 0], [0, 0], [1, 0]]);
+        app.update();
+        assert_eq!(alive(&mut app.world), vec![[0, -1], [0, 0], [0, 1]]);
+        assert_eq!(
+            app.world
+                .resource::<CellularAutomaton<TestLayer, Life>>()
+                .generation(),
+            2
+        );
+    }
+
+    #[derive(Component)]
+    struct Wall;
+
+    #[test]
+    fn keeps_stateless_cells() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellsPlugin,
+            CellularAutomatonPlugin::<TestLayer, Life>::default(),
+        ))
+        .insert_resource(Life);
+
+        // [1, 0] would be born from the three live cells around it
+        let mut cells = app.world.cells::<TestLayer, 2>();
+        for cell_c in [[0, -1], [0, 0], [0, 1]] {
+            cells.spawn_cell(cell_c, Alive).unwrap();
+        }
+        let wall = cells.spawn_cell([1, 0], Wall).unwrap();
+
+        app.update();
+        assert!(app.world.get::<Wall>(wall).is_some());
+        assert_eq!(app.world.cells::<TestLayer, 2>().get_at([1, 0]), Some(wall));
+        assert_eq!(
+            app.world
+                .query_filtered::<&CellCoord, With<Alive>>()
+                .iter(&app.world)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn stays_in_bounds() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellsPlugin,
+            CellularAutomatonPlugin::<TestLayer, Life>::default(),
+        ))
+        .insert_resource(Life);
+
+        // A blinker on the edge of a clamped map would be born onto its own middle cell
+        let config = CellMapConfig::<TestLayer>::default()
+            .with_bounds([0, 0], [2, 2])
+            .with_bounds_policy(BoundsPolicy::Clamp);
+        let map_id = app.world.spawn(CellMap::new(config)).id();
+        let mut cells = app.world.cells_on::<TestLayer, 2>(map_id);
+        for cell_c in [[2, 0], [2, 2]] {
+            cells.spawn_cell(cell_c, Alive).unwrap();
+        }
+        let middle = cells.spawn_cell([2, 1], Alive).unwrap();
+
+        app.update();
+        let mut cells = app.world.cells_on::<TestLayer, 2>(map_id);
+        assert_eq!(cells.get_at([2, 1]), Some(middle));
+        assert!(cells.get_at([1, 1]).is_some());
+        assert_eq!(
+            app.world
+                .query_filtered::<&CellCoord, With<Alive>>()
+                .iter(&app.world)
+                .count(),
+            2
+        );
+    }
+}
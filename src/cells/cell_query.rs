@@ -6,7 +6,8 @@ use bevy::{
         query::{ReadOnlyWorldQuery, WorldQuery},
         system::SystemParam,
     },
-    prelude::Query,
+    prelude::{Entity, Query},
+    tasks::{ComputeTaskPool, TaskPool},
 };
 
 use super::{CellMap, CellMapLabel, Chunk, InChunk, InMap};
@@ -57,33 +58,82 @@ where
     Q: WorldQuery + 'static,
     F: ReadOnlyWorldQuery + 'static,
 {
+    /// Get's the map this query should resolve cells from.
+    /// If no map entity is given, the single map for the label is used.
+    #[inline]
+    fn get_map(&self, map_id: Option<Entity>) -> Option<&CellMap<L, N>> {
+        if let Some(map_id) = map_id {
+            self.map_q.get(map_id).ok()
+        } else {
+            self.map_q.get_single().ok()
+        }
+    }
+
+    /// Get's the entity of the cell at the given coordinate.
+    #[inline]
+    fn get_cell_id(&self, map_id: Option<Entity>, cell_c: [isize; N]) -> Option<Entity> {
+        let map = self.get_map(map_id)?;
+        let chunk_size = map.config.chunk_size();
+        let chunk_c = calculate_chunk_coordinate(cell_c, chunk_size);
+        let chunk_e = map.chunks.get(&chunk_c.into())?;
+
+        let chunk = self.chunk_q.get(*chunk_e).ok()?;
+        let cell_i = calculate_cell_index(cell_c, chunk_size);
+        chunk.cells.get(cell_i)?.as_ref().cloned()
+    }
+
+    /// Get's the lowest and highest cell in the map, or `None` if the map is unbounded or missing.
+    pub fn bounds(&self) -> Option<([isize; N], [isize; N])> {
+        self.get_map(None)?.config.bounds()
+    }
+
+    /// Returns true if the coordinate is inside the bounds of the map.
+    /// # Note
+    /// Unbounded maps, and labels without a map, contain every coordinate.
+    pub fn contains(&self, cell_c: [isize; N]) -> bool {
+        match self.get_map(None) {
+            Some(map) => map.config.contains(cell_c),
+            None => true,
+        }
+    }
+
+    /// Casts a ray through the given map, returning the first cell that passes the filter.
+    #[inline]
+    fn raycast_on(
+        &self,
+        map_id: Option<Entity>,
+        origin: [f32; N],
+        dir: [f32; N],
+        max_dist: f32,
+        mut filter: impl FnMut(<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>) -> bool,
+    ) -> Option<GridRayHit<N>> {
+        let mut ray = GridRay::new(origin, dir, max_dist);
+        while let Some(cell_c) = ray.next() {
+            let Some(cell_e) = self.get_cell_id(map_id, cell_c) else {
+                continue;
+            };
+            if self.cell_q.get(cell_e).is_ok_and(&mut filter) {
+                return Some(ray.hit());
+            }
+        }
+        None
+    }
+
     /// Get's the readonly query item for the given cell.
     pub fn get_at(
         &self,
         cell_c: [isize; N],
     ) -> Option<<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>> {
-        let map = self.map_q.get_single().ok()?;
-        let chunk_c = calculate_chunk_coordinate(cell_c, L::CHUNK_SIZE);
-        let chunk_e = map.chunks.get(&chunk_c.into())?;
-
-        let chunk = self.chunk_q.get(*chunk_e).ok()?;
-        let cell_index = calculate_cell_index(cell_c, L::CHUNK_SIZE);
-        let cell_e = chunk.cells.get(cell_index)?.as_ref()?;
+        let cell_e = self.get_cell_id(None, cell_c)?;
 
-        self.cell_q.get(*cell_e).ok()
+        self.cell_q.get(cell_e).ok()
     }
 
     /// Get's the query item for the given cell.
     pub fn get_at_mut(&mut self, cell_c: [isize; N]) -> Option<<Q as WorldQuery>::Item<'_>> {
-        let map = self.map_q.get_single().ok()?;
-        let chunk_c = calculate_chunk_coordinate(cell_c, L::CHUNK_SIZE);
-        let chunk_e = map.chunks.get(&chunk_c.into())?;
-
-        let chunk = self.chunk_q.get(*chunk_e).ok()?;
-        let cell_i = calculate_cell_index(cell_c, L::CHUNK_SIZE);
-        let cell_e = chunk.cells.get(cell_i)?.as_ref()?;
+        let cell_e = self.get_cell_id(None, cell_c)?;
 
-        self.cell_q.get_mut(*cell_e).ok()
+        self.cell_q.get_mut(cell_e).ok()
     }
 
     /// Get's the query item for the given cell.
@@ -93,35 +143,33 @@ where
         &self,
         cell_c: [isize; N],
     ) -> Option<<Q as WorldQuery>::Item<'_>> {
-        let map = self.map_q.get_single().ok()?;
-        let chunk_c = calculate_chunk_coordinate(cell_c, L::CHUNK_SIZE);
-        let chunk_e = map.chunks.get(&chunk_c.into())?;
-
-        let chunk = self.chunk_q.get(*chunk_e).ok()?;
-        let cell_i = calculate_cell_index(cell_c, L::CHUNK_SIZE);
-        let cell_e = chunk.cells.get(cell_i)?.as_ref()?;
+        let cell_e = self.get_cell_id(None, cell_c)?;
 
-        self.cell_q.get_unchecked(*cell_e).ok()
+        self.cell_q.get_unchecked(cell_e).ok()
     }
 
     /// Iterate over all the cells in a given space, starting at `corner_1`
     /// inclusive over `corner_2`
+    /// # Note
+    /// Cells are visited chunk by chunk, and chunks that don't exist are skipped.
     pub fn iter_in(
         &self,
         corner_1: [isize; N],
         corner_2: [isize; N],
     ) -> CellQueryIter<'_, 's, L, Q, F, N> {
-        unsafe { CellQueryIter::new(self, corner_1, corner_2) }
+        unsafe { CellQueryIter::new(self, None, corner_1, corner_2) }
     }
 
     /// Iterate over all the cells in a given space, starting at `corner_1`
     /// inclusive over `corner_2`
+    /// # Note
+    /// Cells are visited chunk by chunk, and chunks that don't exist are skipped.
     pub fn iter_in_mut(
         &mut self,
         corner_1: [isize; N],
         corner_2: [isize; N],
     ) -> CellQueryIterMut<'_, 's, L, Q, F, N> {
-        unsafe { CellQueryIterMut::new(self, corner_1, corner_2) }
+        unsafe { CellQueryIterMut::new(self, None, corner_1, corner_2) }
     }
 
     pub fn to_readonly(
@@ -138,24 +186,16 @@ where
     /// # Note
     /// The coordinates for this function are givne in chunk coordinates.
     pub fn iter_in_chunk(&self, chunk_c: [isize; N]) -> CellQueryIter<'_, 's, L, Q, F, N> {
-        // Get corners of chunk
-        let corner_1 = calculate_cell_coordinate(chunk_c, 0, L::CHUNK_SIZE);
-        let corner_2 =
-            calculate_cell_coordinate(chunk_c, max_cell_index::<N>(L::CHUNK_SIZE), L::CHUNK_SIZE);
         // Create cell iter
-        unsafe { CellQueryIter::new(self, corner_1, corner_2) }
+        unsafe { CellQueryIter::in_chunks(self, None, chunk_c, chunk_c) }
     }
 
     /// Iter all cells in a given chunk.
     /// # Note
     /// The coordinates for this function are givne in chunk coordinates.
     pub fn iter_in_chunk_mut(&self, chunk_c: [isize; N]) -> CellQueryIterMut<'_, 's, L, Q, F, N> {
-        // Get corners of chunk
-        let corner_1 = calculate_cell_coordinate(chunk_c, 0, L::CHUNK_SIZE);
-        let corner_2 =
-            calculate_cell_coordinate(chunk_c, max_cell_index::<N>(L::CHUNK_SIZE), L::CHUNK_SIZE);
         // Create cell iter
-        unsafe { CellQueryIterMut::new(self, corner_1, corner_2) }
+        unsafe { CellQueryIterMut::in_chunks(self, None, chunk_c, chunk_c) }
     }
 
     /// Iter all cells in the chunks in the given range.
@@ -166,12 +206,8 @@ where
         chunk_c_1: [isize; N],
         chunk_c_2: [isize; N],
     ) -> CellQueryIter<'_, 's, L, Q, F, N> {
-        // Get corners of chunk
-        let corner_1 = calculate_cell_coordinate(chunk_c_1, 0, L::CHUNK_SIZE);
-        let corner_2 =
-            calculate_cell_coordinate(chunk_c_2, max_cell_index::<N>(L::CHUNK_SIZE), L::CHUNK_SIZE);
         // Create cell iter
-        unsafe { CellQueryIter::new(self, corner_1, corner_2) }
+        unsafe { CellQueryIter::in_chunks(self, None, chunk_c_1, chunk_c_2) }
     }
 
     /// Iter all cells in the chunks in the given range.
@@ -182,22 +218,634 @@ where
         chunk_c_1: [isize; N],
         chunk_c_2: [isize; N],
     ) -> CellQueryIterMut<'_, 's, L, Q, F, N> {
-        // Get corners of chunk
-        let corner_1 = calculate_cell_coordinate(chunk_c_1, 0, L::CHUNK_SIZE);
-        let corner_2 =
-            calculate_cell_coordinate(chunk_c_2, max_cell_index::<N>(L::CHUNK_SIZE), L::CHUNK_SIZE);
         // Create cell iter
-        unsafe { CellQueryIterMut::new(self, corner_1, corner_2) }
+        unsafe { CellQueryIterMut::in_chunks(self, None, chunk_c_1, chunk_c_2) }
+    }
+
+    /// Runs a function over all the cells in a given space in parallel, starting at `corner_1`
+    /// inclusive over `corner_2`.
+    /// # Note
+    /// Work is split by chunk, so small regions are better served by [iter_in](Self::iter_in).
+    pub fn par_iter_in(
+        &self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> CellQueryParIter<'_, 's, L, Q, F, N> {
+        let (chunk_size, chunks) =
+            ChunkWalk::new(self.get_map(None), corner_1, corner_2).into_chunks(&self.chunk_q);
+        CellQueryParIter {
+            cell_q: self,
+            chunk_size,
+            chunks,
+        }
+    }
+
+    /// Runs a function over all the cells in a given space in parallel with mutable access,
+    /// starting at `corner_1` inclusive over `corner_2`.
+    pub fn par_iter_in_mut(
+        &mut self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> CellQueryParIterMut<'_, 's, L, Q, F, N> {
+        let cell_q = &*self;
+        let (chunk_size, chunks) =
+            ChunkWalk::new(cell_q.get_map(None), corner_1, corner_2).into_chunks(&cell_q.chunk_q);
+        CellQueryParIterMut {
+            cell_q,
+            chunk_size,
+            chunks,
+        }
+    }
+
+    /// Runs a function over all the cells in the chunks in the given range in parallel.
+    /// # Note
+    /// The coordinates for this function are given in chunk coordinates.
+    pub fn par_iter_in_chunks(
+        &self,
+        chunk_c_1: [isize; N],
+        chunk_c_2: [isize; N],
+    ) -> CellQueryParIter<'_, 's, L, Q, F, N> {
+        let (chunk_size, chunks) = ChunkWalk::in_chunks(self.get_map(None), chunk_c_1, chunk_c_2)
+            .into_chunks(&self.chunk_q);
+        CellQueryParIter {
+            cell_q: self,
+            chunk_size,
+            chunks,
+        }
+    }
+
+    /// Runs a function over all the cells in the chunks in the given range in parallel with mutable access.
+    /// # Note
+    /// The coordinates for this function are given in chunk coordinates.
+    pub fn par_iter_in_chunks_mut(
+        &mut self,
+        chunk_c_1: [isize; N],
+        chunk_c_2: [isize; N],
+    ) -> CellQueryParIterMut<'_, 's, L, Q, F, N> {
+        let cell_q = &*self;
+        let (chunk_size, chunks) = ChunkWalk::in_chunks(cell_q.get_map(None), chunk_c_1, chunk_c_2)
+            .into_chunks(&cell_q.chunk_q);
+        CellQueryParIterMut {
+            cell_q,
+            chunk_size,
+            chunks,
+        }
+    }
+
+    /// Iterate over the cells adjacent to the given cell, along with their coordinates.
+    pub fn neighbors(
+        &self,
+        cell_c: [isize; N],
+        adjacency: Adjacency,
+    ) -> impl Iterator<
+        Item = (
+            [isize; N],
+            <<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>,
+        ),
+    > + '_ {
+        neighbors(cell_c, adjacency).filter_map(|cell_c| {
+            let cell_e = self.get_cell_id(None, cell_c)?;
+            Some((cell_c, self.cell_q.get(cell_e).ok()?))
+        })
+    }
+
+    /// Iterate over the cells adjacent to the given cell, along with their coordinates.
+    pub fn neighbors_mut(
+        &mut self,
+        cell_c: [isize; N],
+        adjacency: Adjacency,
+    ) -> impl Iterator<Item = ([isize; N], <Q as WorldQuery>::Item<'_>)> + '_ {
+        let cell_q = &*self;
+        neighbors(cell_c, adjacency).filter_map(move |cell_c| {
+            let cell_e = cell_q.get_cell_id(None, cell_c)?;
+            // Safety: Each neighbor coordinate is unique, so each cell is only returned once,
+            // and the query is borrowed mutably for the lifetime of the iterator.
+            Some((cell_c, unsafe { cell_q.cell_q.get_unchecked(cell_e).ok()? }))
+        })
+    }
+
+    /// Casts a ray through the map, returning the first cell matched by the query that passes the filter.
+    /// # Note
+    /// The ray is given in cell space, see [GridRay].
+    pub fn raycast(
+        &self,
+        origin: [f32; N],
+        dir: [f32; N],
+        max_dist: f32,
+        filter: impl FnMut(<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>) -> bool,
+    ) -> Option<GridRayHit<N>> {
+        self.raycast_on(None, origin, dir, max_dist, filter)
+    }
+
+    /// Get's a view of this query that resolves cells on the given map entity.
+    /// # Note
+    /// Use this when multiple maps share the same [CellMapLabel].
+    pub fn on_map(&self, map_id: Entity) -> CellQueryOnMap<'_, 'w, 's, L, Q, F, N> {
+        CellQueryOnMap {
+            cell_q: self,
+            map_id,
+        }
+    }
+
+    /// Get's a mutable view of this query that resolves cells on the given map entity.
+    /// # Note
+    /// Use this when multiple maps share the same [CellMapLabel].
+    pub fn on_map_mut(&mut self, map_id: Entity) -> CellQueryOnMapMut<'_, 'w, 's, L, Q, F, N> {
+        CellQueryOnMapMut {
+            cell_q: self,
+            map_id,
+        }
+    }
+}
+
+/// A [CellQuery] that resolves cells on a specific map entity instead
+/// of the single map for its [CellMapLabel].
+pub struct CellQueryOnMap<'a, 'w, 's, L, Q, F, const N: usize>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    cell_q: &'a CellQuery<'w, 's, L, Q, F, N>,
+    map_id: Entity,
+}
+
+impl<'a, 'w, 's, L, Q, F, const N: usize> CellQueryOnMap<'a, 'w, 's, L, Q, F, N>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    /// Get's the map entity this query resolves cells on.
+    pub fn map_id(&self) -> Entity {
+        self.map_id
+    }
+
+    /// Get's the lowest and highest cell in the map, or `None` if the map is unbounded or missing.
+    pub fn bounds(&self) -> Option<([isize; N], [isize; N])> {
+        self.cell_q.get_map(Some(self.map_id))?.config.bounds()
+    }
+
+    /// Returns true if the coordinate is inside the bounds of the map.
+    pub fn contains(&self, cell_c: [isize; N]) -> bool {
+        match self.cell_q.get_map(Some(self.map_id)) {
+            Some(map) => map.config.contains(cell_c),
+            None => true,
+        }
+    }
+
+    /// Get's the readonly query item for the given cell.
+    pub fn get_at(
+        &self,
+        cell_c: [isize; N],
+    ) -> Option<<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>> {
+        let cell_e = self.cell_q.get_cell_id(Some(self.map_id), cell_c)?;
+
+        self.cell_q.cell_q.get(cell_e).ok()
+    }
+
+    /// Iterate over all the cells in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`
+    /// # Note
+    /// Cells are visited chunk by chunk, and chunks that don't exist are skipped.
+    pub fn iter_in(
+        &self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> CellQueryIter<'_, 's, L, Q, F, N> {
+        unsafe { CellQueryIter::new(self.cell_q, Some(self.map_id), corner_1, corner_2) }
+    }
+
+    /// Iter all cells in a given chunk.
+    /// # Note
+    /// The coordinates for this function are givne in chunk coordinates.
+    pub fn iter_in_chunk(&self, chunk_c: [isize; N]) -> CellQueryIter<'_, 's, L, Q, F, N> {
+        unsafe { CellQueryIter::in_chunks(self.cell_q, Some(self.map_id), chunk_c, chunk_c) }
+    }
+
+    /// Iter all cells in the chunks in the given range.
+    /// # Note
+    /// The coordinates for this function are givne in chunk coordinates.
+    pub fn iter_in_chunks(
+        &self,
+        chunk_c_1: [isize; N],
+        chunk_c_2: [isize; N],
+    ) -> CellQueryIter<'_, 's, L, Q, F, N> {
+        unsafe { CellQueryIter::in_chunks(self.cell_q, Some(self.map_id), chunk_c_1, chunk_c_2) }
+    }
+
+    /// Iterate over the cells adjacent to the given cell, along with their coordinates.
+    pub fn neighbors(
+        &self,
+        cell_c: [isize; N],
+        adjacency: Adjacency,
+    ) -> impl Iterator<
+        Item = (
+            [isize; N],
+            <<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>,
+        ),
+    > + '_ {
+        let map_id = self.map_id;
+        let cell_q = self.cell_q;
+        neighbors(cell_c, adjacency).filter_map(move |cell_c| {
+            let cell_e = cell_q.get_cell_id(Some(map_id), cell_c)?;
+            Some((cell_c, cell_q.cell_q.get(cell_e).ok()?))
+        })
+    }
+
+    /// Casts a ray through the map, returning the first cell matched by the query that passes the filter.
+    /// # Note
+    /// The ray is given in cell space, see [GridRay].
+    pub fn raycast(
+        &self,
+        origin: [f32; N],
+        dir: [f32; N],
+        max_dist: f32,
+        filter: impl FnMut(<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>) -> bool,
+    ) -> Option<GridRayHit<N>> {
+        self.cell_q
+            .raycast_on(Some(self.map_id), origin, dir, max_dist, filter)
     }
 }
 
+/// A mutable [CellQuery] that resolves cells on a specific map entity instead
+/// of the single map for its [CellMapLabel].
+pub struct CellQueryOnMapMut<'a, 'w, 's, L, Q, F, const N: usize>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    cell_q: &'a mut CellQuery<'w, 's, L, Q, F, N>,
+    map_id: Entity,
+}
+
+impl<'a, 'w, 's, L, Q, F, const N: usize> CellQueryOnMapMut<'a, 'w, 's, L, Q, F, N>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    /// Get's the map entity this query resolves cells on.
+    pub fn map_id(&self) -> Entity {
+        self.map_id
+    }
+
+    /// Get's the lowest and highest cell in the map, or `None` if the map is unbounded or missing.
+    pub fn bounds(&self) -> Option<([isize; N], [isize; N])> {
+        self.cell_q.get_map(Some(self.map_id))?.config.bounds()
+    }
+
+    /// Returns true if the coordinate is inside the bounds of the map.
+    pub fn contains(&self, cell_c: [isize; N]) -> bool {
+        match self.cell_q.get_map(Some(self.map_id)) {
+            Some(map) => map.config.contains(cell_c),
+            None => true,
+        }
+    }
+
+    /// Get's the readonly query item for the given cell.
+    pub fn get_at(
+        &self,
+        cell_c: [isize; N],
+    ) -> Option<<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>> {
+        let cell_e = self.cell_q.get_cell_id(Some(self.map_id), cell_c)?;
+
+        self.cell_q.cell_q.get(cell_e).ok()
+    }
+
+    /// Iterate over all the cells in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`
+    /// # Note
+    /// Cells are visited chunk by chunk, and chunks that don't exist are skipped.
+    pub fn iter_in(
+        &self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> CellQueryIter<'_, 's, L, Q, F, N> {
+        unsafe { CellQueryIter::new(self.cell_q, Some(self.map_id), corner_1, corner_2) }
+    }
+
+    /// Iter all cells in a given chunk.
+    /// # Note
+    /// The coordinates for this function are givne in chunk coordinates.
+    pub fn iter_in_chunk(&self, chunk_c: [isize; N]) -> CellQueryIter<'_, 's, L, Q, F, N> {
+        unsafe { CellQueryIter::in_chunks(self.cell_q, Some(self.map_id), chunk_c, chunk_c) }
+    }
+
+    /// Iter all cells in the chunks in the given range.
+    /// # Note
+    /// The coordinates for this function are givne in chunk coordinates.
+    pub fn iter_in_chunks(
+        &self,
+        chunk_c_1: [isize; N],
+        chunk_c_2: [isize; N],
+    ) -> CellQueryIter<'_, 's, L, Q, F, N> {
+        unsafe { CellQueryIter::in_chunks(self.cell_q, Some(self.map_id), chunk_c_1, chunk_c_2) }
+    }
+
+    /// Iterate over the cells adjacent to the given cell, along with their coordinates.
+    pub fn neighbors(
+        &self,
+        cell_c: [isize; N],
+        adjacency: Adjacency,
+    ) -> impl Iterator<
+        Item = (
+            [isize; N],
+            <<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>,
+        ),
+    > + '_ {
+        let map_id = self.map_id;
+        let cell_q = &*self.cell_q;
+        neighbors(cell_c, adjacency).filter_map(move |cell_c| {
+            let cell_e = cell_q.get_cell_id(Some(map_id), cell_c)?;
+            Some((cell_c, cell_q.cell_q.get(cell_e).ok()?))
+        })
+    }
+
+    /// Casts a ray through the map, returning the first cell matched by the query that passes the filter.
+    /// # Note
+    /// The ray is given in cell space, see [GridRay].
+    pub fn raycast(
+        &self,
+        origin: [f32; N],
+        dir: [f32; N],
+        max_dist: f32,
+        filter: impl FnMut(<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>) -> bool,
+    ) -> Option<GridRayHit<N>> {
+        self.cell_q
+            .raycast_on(Some(self.map_id), origin, dir, max_dist, filter)
+    }
+
+    /// Get's the query item for the given cell.
+    pub fn get_at_mut(&mut self, cell_c: [isize; N]) -> Option<<Q as WorldQuery>::Item<'_>> {
+        let cell_e = self.cell_q.get_cell_id(Some(self.map_id), cell_c)?;
+
+        self.cell_q.cell_q.get_mut(cell_e).ok()
+    }
+
+    /// Iterate over all the cells in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`
+    /// # Note
+    /// Cells are visited chunk by chunk, and chunks that don't exist are skipped.
+    pub fn iter_in_mut(
+        &mut self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> CellQueryIterMut<'_, 's, L, Q, F, N> {
+        unsafe { CellQueryIterMut::new(self.cell_q, Some(self.map_id), corner_1, corner_2) }
+    }
+
+    /// Iter all cells in a given chunk.
+    /// # Note
+    /// The coordinates for this function are givne in chunk coordinates.
+    pub fn iter_in_chunk_mut(
+        &mut self,
+        chunk_c: [isize; N],
+    ) -> CellQueryIterMut<'_, 's, L, Q, F, N> {
+        unsafe { CellQueryIterMut::in_chunks(self.cell_q, Some(self.map_id), chunk_c, chunk_c) }
+    }
+
+    /// Iter all cells in the chunks in the given range.
+    /// # Note
+    /// The coordinates for this function are givne in chunk coordinates.
+    pub fn iter_in_chunks_mut(
+        &mut self,
+        chunk_c_1: [isize; N],
+        chunk_c_2: [isize; N],
+    ) -> CellQueryIterMut<'_, 's, L, Q, F, N> {
+        unsafe { CellQueryIterMut::in_chunks(self.cell_q, Some(self.map_id), chunk_c_1, chunk_c_2) }
+    }
+
+    /// Iterate over the cells adjacent to the given cell, along with their coordinates.
+    pub fn neighbors_mut(
+        &mut self,
+        cell_c: [isize; N],
+        adjacency: Adjacency,
+    ) -> impl Iterator<Item = ([isize; N], <Q as WorldQuery>::Item<'_>)> + '_ {
+        let map_id = self.map_id;
+        let cell_q = &*self.cell_q;
+        neighbors(cell_c, adjacency).filter_map(move |cell_c| {
+            let cell_e = cell_q.get_cell_id(Some(map_id), cell_c)?;
+            // Safety: Each neighbor coordinate is unique, so each cell is only returned once,
+            // and the query is borrowed mutably for the lifetime of the iterator.
+            Some((cell_c, unsafe { cell_q.cell_q.get_unchecked(cell_e).ok()? }))
+        })
+    }
+}
+
+/// Runs a function over the cells in a region in parallel, splitting the work by chunk.
+pub struct CellQueryParIter<'w, 's, L, Q, F, const N: usize>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    cell_q: &'w CellQuery<'w, 's, L, Q, F, N>,
+    chunk_size: [usize; N],
+    chunks: Vec<(&'w Chunk, CoordIterator<N>)>,
+}
+
+impl<'w, 's, L, Q, F, const N: usize> CellQueryParIter<'w, 's, L, Q, F, N>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    /// Runs the function on every cell, each chunk is handled by a task on the [ComputeTaskPool].
+    pub fn for_each<FN>(self, f: FN)
+    where
+        FN: Fn(<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'w>) + Send + Sync,
+    {
+        let (cell_q, chunk_size) = (self.cell_q, self.chunk_size);
+        let f = &f;
+        ComputeTaskPool::get_or_init(TaskPool::default).scope(|scope| {
+            for (chunk, mut cell_iter) in self.chunks {
+                scope.spawn(async move {
+                    while let Some(cell_e) = next_in_chunk(chunk, chunk_size, &mut cell_iter) {
+                        if let Ok(cell) = cell_q.cell_q.get(cell_e) {
+                            f(cell);
+                        }
+                    }
+                });
+            }
+        });
+    }
+}
+
+/// Runs a function over the cells in a region in parallel with mutable access, splitting the work by chunk.
+pub struct CellQueryParIterMut<'w, 's, L, Q, F, const N: usize>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    cell_q: &'w CellQuery<'w, 's, L, Q, F, N>,
+    chunk_size: [usize; N],
+    chunks: Vec<(&'w Chunk, CoordIterator<N>)>,
+}
+
+impl<'w, 's, L, Q, F, const N: usize> CellQueryParIterMut<'w, 's, L, Q, F, N>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    /// Runs the function on every cell, each chunk is handled by a task on the [ComputeTaskPool].
+    pub fn for_each<FN>(self, f: FN)
+    where
+        FN: Fn(<Q as WorldQuery>::Item<'w>) + Send + Sync,
+    {
+        let (cell_q, chunk_size) = (self.cell_q, self.chunk_size);
+        let f = &f;
+        ComputeTaskPool::get_or_init(TaskPool::default).scope(|scope| {
+            for (chunk, mut cell_iter) in self.chunks {
+                scope.spawn(async move {
+                    while let Some(cell_e) = next_in_chunk(chunk, chunk_size, &mut cell_iter) {
+                        // Safety: A cell is only in one slot of one chunk, so every task gets different cells,
+                        // and the query is borrowed mutably for the lifetime of the iterator.
+                        if let Ok(cell) = unsafe { cell_q.cell_q.get_unchecked(cell_e) } {
+                            f(cell);
+                        }
+                    }
+                });
+            }
+        });
+    }
+}
+
+/// Walks the chunks overlapping a region once, scanning the cells of each chunk
+/// that are inside the region and skipping missing chunks entirely.
+struct ChunkWalk<'w, L, const N: usize>
+where
+    L: CellMapLabel + 'static,
+{
+    map: Option<&'w CellMap<L, N>>,
+    chunk_size: [usize; N],
+    chunk_iter: CoordIterator<N>,
+    corner_1: [isize; N],
+    corner_2: [isize; N],
+    current: Option<(&'w Chunk, CoordIterator<N>)>,
+}
+
+impl<'w, L, const N: usize> ChunkWalk<'w, L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    fn new(map: Option<&'w CellMap<L, N>>, corner_1: [isize; N], corner_2: [isize; N]) -> Self {
+        let (mut corner_1, mut corner_2) = (corner_1, corner_2);
+        for i in 0..N {
+            if corner_1[i] > corner_2[i] {
+                std::mem::swap(&mut corner_1[i], &mut corner_2[i]);
+            }
+        }
+        let chunk_size = map.map_or_else(L::chunk_size, |map| map.config.chunk_size());
+        Self {
+            map,
+            chunk_size,
+            chunk_iter: CoordIterator::new(
+                calculate_chunk_coordinate(corner_1, chunk_size),
+                calculate_chunk_coordinate(corner_2, chunk_size),
+            ),
+            corner_1,
+            corner_2,
+            current: None,
+        }
+    }
+
+    fn in_chunks(
+        map: Option<&'w CellMap<L, N>>,
+        chunk_c_1: [isize; N],
+        chunk_c_2: [isize; N],
+    ) -> Self {
+        Self {
+            map,
+            chunk_size: map.map_or_else(L::chunk_size, |map| map.config.chunk_size()),
+            chunk_iter: CoordIterator::new(chunk_c_1, chunk_c_2),
+            corner_1: [isize::MIN; N],
+            corner_2: [isize::MAX; N],
+            current: None,
+        }
+    }
+
+    #[inline]
+    fn next<Fc>(&mut self, chunk_q: &'w Query<'w, '_, &'static Chunk, Fc>) -> Option<Entity>
+    where
+        Fc: ReadOnlyWorldQuery + 'static,
+    {
+        loop {
+            if let Some((chunk, cell_iter)) = self.current.as_mut() {
+                if let Some(cell_e) = next_in_chunk(chunk, self.chunk_size, cell_iter) {
+                    return Some(cell_e);
+                }
+            }
+            self.current = Some(self.next_chunk(chunk_q)?);
+        }
+    }
+
+    /// Gets the next chunk overlapping the region, along with the cells in it to scan.
+    #[inline]
+    fn next_chunk<Fc>(
+        &mut self,
+        chunk_q: &'w Query<'w, '_, &'static Chunk, Fc>,
+    ) -> Option<(&'w Chunk, CoordIterator<N>)>
+    where
+        Fc: ReadOnlyWorldQuery + 'static,
+    {
+        loop {
+            let chunk_c = self.chunk_iter.next()?;
+            let Some(chunk) = self
+                .map?
+                .chunks
+                .get(&chunk_c.into())
+                .and_then(|chunk_e| chunk_q.get(*chunk_e).ok())
+            else {
+                continue;
+            };
+
+            // Only scan the part of the chunk inside the region
+            let (mut corner_1, mut corner_2) = (self.corner_1, self.corner_2);
+            let (min, max) = calculate_chunk_cell_range(chunk_c, self.chunk_size);
+            for i in 0..N {
+                corner_1[i] = corner_1[i].max(min[i]);
+                corner_2[i] = corner_2[i].min(max[i]);
+            }
+            if (0..N).all(|i| corner_1[i] <= corner_2[i]) {
+                return Some((chunk, CoordIterator::new(corner_1, corner_2)));
+            }
+        }
+    }
+
+    /// Collects every chunk overlapping the region along with the chunk size, to split them between tasks.
+    fn into_chunks<Fc>(
+        mut self,
+        chunk_q: &'w Query<'w, '_, &'static Chunk, Fc>,
+    ) -> ([usize; N], Vec<(&'w Chunk, CoordIterator<N>)>)
+    where
+        Fc: ReadOnlyWorldQuery + 'static,
+    {
+        let chunks = std::iter::from_fn(|| self.next_chunk(chunk_q)).collect();
+        (self.chunk_size, chunks)
+    }
+}
+
+/// Gets the next cell in a chunk from the cells left to scan.
+#[inline]
+fn next_in_chunk<const N: usize>(
+    chunk: &Chunk,
+    chunk_size: [usize; N],
+    cell_iter: &mut CoordIterator<N>,
+) -> Option<Entity> {
+    cell_iter.find_map(|cell_c| *chunk.cells.get(calculate_cell_index(cell_c, chunk_size))?)
+}
+
 pub struct CellQueryIter<'w, 's, L, Q, F, const N: usize>
 where
     L: CellMapLabel + 'static,
     Q: WorldQuery + 'static,
     F: ReadOnlyWorldQuery + 'static,
 {
-    coord_iter: CoordIterator<N>,
+    walk: ChunkWalk<'w, L, N>,
     cell_q: &'w CellQuery<'w, 's, L, Q, F, N>,
 }
 
@@ -213,12 +861,27 @@ where
     /// borrowed mutabley.
     unsafe fn new(
         cell_q: &'w CellQuery<'w, 's, L, Q, F, N>,
+        map_id: Option<Entity>,
         corner_1: [isize; N],
         corner_2: [isize; N],
     ) -> Self {
         Self {
+            walk: ChunkWalk::new(cell_q.get_map(map_id), corner_1, corner_2),
+            cell_q,
+        }
+    }
+
+    /// # Safety
+    /// See [new](Self::new).
+    unsafe fn in_chunks(
+        cell_q: &'w CellQuery<'w, 's, L, Q, F, N>,
+        map_id: Option<Entity>,
+        chunk_c_1: [isize; N],
+        chunk_c_2: [isize; N],
+    ) -> Self {
+        Self {
+            walk: ChunkWalk::in_chunks(cell_q.get_map(map_id), chunk_c_1, chunk_c_2),
             cell_q,
-            coord_iter: CoordIterator::new(corner_1, corner_2),
         }
     }
 }
@@ -231,13 +894,12 @@ where
 {
     type Item = <<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'w>;
 
-    #[allow(clippy::while_let_on_iterator)]
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(target) = self.coord_iter.next() {
-            // This fixes some lifetime issue that I'm not sure I understand quite yet, will do testing
-            let cell = self.cell_q.get_at(target);
-            if cell.is_some() {
-                return cell;
+        // This fixes some lifetime issue that I'm not sure I understand quite yet, will do testing
+        let cell_q = self.cell_q;
+        while let Some(cell_e) = self.walk.next(&cell_q.chunk_q) {
+            if let Ok(cell) = cell_q.cell_q.get(cell_e) {
+                return Some(cell);
             }
         }
 
@@ -260,7 +922,7 @@ where
     Q: WorldQuery + 'static,
     F: ReadOnlyWorldQuery + 'static,
 {
-    coord_iter: CoordIterator<N>,
+    walk: ChunkWalk<'w, L, N>,
     cell_q: &'w CellQuery<'w, 's, L, Q, F, N>,
 }
 
@@ -276,12 +938,27 @@ where
     /// borrowed mutabley.
     unsafe fn new(
         cell_q: &'w CellQuery<'w, 's, L, Q, F, N>,
+        map_id: Option<Entity>,
         corner_1: [isize; N],
         corner_2: [isize; N],
     ) -> Self {
         Self {
+            walk: ChunkWalk::new(cell_q.get_map(map_id), corner_1, corner_2),
+            cell_q,
+        }
+    }
+
+    /// # Safety
+    /// See [new](Self::new).
+    unsafe fn in_chunks(
+        cell_q: &'w CellQuery<'w, 's, L, Q, F, N>,
+        map_id: Option<Entity>,
+        chunk_c_1: [isize; N],
+        chunk_c_2: [isize; N],
+    ) -> Self {
+        Self {
+            walk: ChunkWalk::in_chunks(cell_q.get_map(map_id), chunk_c_1, chunk_c_2),
             cell_q,
-            coord_iter: CoordIterator::new(corner_1, corner_2),
         }
     }
 }
@@ -294,16 +971,173 @@ where
 {
     type Item = <Q as WorldQuery>::Item<'w>;
 
-    #[allow(clippy::while_let_on_iterator)]
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(target) = self.coord_iter.next() {
-            // This fixes some lifetime issue that I'm not sure I understand quite yet, will do testing
-            let cell = unsafe { self.cell_q.get_at_unchecked(target) };
-            if cell.is_some() {
-                return cell;
+        // This fixes some lifetime issue that I'm not sure I understand quite yet, will do testing
+        let cell_q = self.cell_q;
+        while let Some(cell_e) = self.walk.next(&cell_q.chunk_q) {
+            // Safety: Each cell is only returned once, and the query is borrowed mutably for the lifetime of the iterator.
+            if let Ok(cell) = unsafe { cell_q.cell_q.get_unchecked(cell_e) } {
+                return Some(cell);
             }
         }
 
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use bevy::ecs::{
+        system::{CommandQueue, Commands, SystemState},
+        world::World,
+    };
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        cells::{
+            chunk_query::ChunkQuery,
+            commands::{CellCommandExt, CellWorldExt},
+            CellCoord, CellMapConfig, InsertPolicy,
+        },
+        CellsPlugin,
+    };
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 4;
+    }
+
+    #[rstest]
+    #[case([-9, -5], [6, 3])]
+    #[case([3, 7], [-4, -8])]
+    #[case([-16, -16], [-16, -16])]
+    #[case([20, 20], [30, 30])]
+    fn iter_in_matches_region(#[case] corner_1: [isize; 2], #[case] corner_2: [isize; 2]) {
+        let mut app = bevy::app::App::new();
+        app.add_plugins(CellsPlugin);
+        let world: &mut World = &mut app.world;
+        // Leave gaps so some chunks are sparse and some are missing
+        for cell_c in CoordIterator::new([-17, -17], [10, 10]).filter(|[x, y]| (x + y) % 3 != 0) {
+            world
+                .cells::<TestLayer, 2>()
+                .spawn_cell(cell_c, ())
+                .unwrap();
+        }
+
+        let mut state = SystemState::<CellQuery<TestLayer, &CellCoord>>::new(world);
+        let cell_q = state.get(world);
+        let found = cell_q
+            .iter_in(corner_1, corner_2)
+            .map(|cell_c| **cell_c)
+            .collect::<Vec<_>>();
+        let expected = CoordIterator::new(corner_1, corner_2)
+            .filter(|cell_c| cell_q.get_at(*cell_c).is_some())
+            .collect::<HashSet<_>>();
+        assert_eq!(found.len(), expected.len());
+        assert_eq!(found.into_iter().collect::<HashSet<_>>(), expected);
+
+        let chunk_c = calculate_chunk_coordinate(corner_1, TestLayer::chunk_size());
+        assert!(cell_q
+            .iter_in_chunk(chunk_c)
+            .all(
+                |cell_c| calculate_chunk_coordinate(**cell_c, TestLayer::chunk_size()) == chunk_c
+            ));
+    }
+
+    #[derive(bevy::ecs::component::Component)]
+    struct Count(usize);
+
+    #[test]
+    fn par_iter_in_visits_region() {
+        let mut app = bevy::app::App::new();
+        app.add_plugins(CellsPlugin);
+        let world: &mut World = &mut app.world;
+        for cell_c in CoordIterator::new([-10, -10], [10, 10]) {
+            world
+                .cells::<TestLayer, 2>()
+                .spawn_cell(cell_c, Count(0))
+                .unwrap();
+        }
+
+        let mut state = SystemState::<CellQuery<TestLayer, &mut Count>>::new(world);
+        let mut cell_q = state.get_mut(world);
+        cell_q
+            .par_iter_in_mut([-6, -2], [3, 9])
+            .for_each(|mut count| count.0 += 1);
+        cell_q
+            .par_iter_in_chunks_mut([0, 0], [0, 0])
+            .for_each(|mut count| count.0 += 10);
+
+        let counts = std::sync::atomic::AtomicUsize::new(0);
+        cell_q.par_iter_in([-10, -10], [10, 10]).for_each(|count| {
+            counts.fetch_add(count.0, std::sync::atomic::Ordering::Relaxed);
+        });
+        assert_eq!(counts.into_inner(), 10 * 12 + 16 * 10);
+    }
+
+    #[test]
+    fn runtime_config() {
+        let mut world = World::new();
+        let config = CellMapConfig::<TestLayer>::default()
+            .with_chunk_size([3, 5])
+            .with_insert_policy(InsertPolicy::Reject);
+        let map_id = world.spawn(CellMap::new(config)).id();
+        let mut cells = world.cells_on::<TestLayer, 2>(map_id);
+        for cell_c in CoordIterator::new([-7, -7], [7, 7]) {
+            cells.spawn_cell(cell_c, ()).unwrap();
+        }
+        assert!(cells.spawn_cell([2, 2], ()).is_err());
+
+        let chunk = world.query::<&Chunk>().iter(&world).next().unwrap();
+        assert_eq!(chunk.cells.len(), 15);
+        let mut state = SystemState::<CellQuery<TestLayer, &CellCoord>>::new(&mut world);
+        let cell_q = state.get(&world);
+        assert_eq!(cell_q.get_at([-6, 4]).map(|cell_c| **cell_c), Some([-6, 4]));
+        assert_eq!(cell_q.iter_in([-4, -6], [5, 2]).count(), 10 * 9);
+    }
+
+    #[test]
+    fn maps_sharing_a_label_stay_separate() {
+        let mut world = World::new();
+        let map_1 = world.spawn(CellMap::<TestLayer>::default()).id();
+        let map_2 = world.spawn(CellMap::<TestLayer>::default()).id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let cell_1 = commands
+            .cells_on::<TestLayer, 2>(map_1)
+            .spawn_cell([1, 1], ())
+            .id();
+        let cell_2 = commands
+            .cells_on::<TestLayer, 2>(map_2)
+            .spawn_cell([1, 1], ())
+            .id();
+        queue.apply(&mut world);
+
+        let mut state =
+            SystemState::<(CellQuery<TestLayer, Entity>, ChunkQuery<TestLayer, Entity>)>::new(
+                &mut world,
+            );
+        let (cell_q, chunk_q) = state.get(&world);
+        assert_ne!(
+            chunk_q.on_map(map_1).get_at([0, 0]),
+            chunk_q.on_map(map_2).get_at([0, 0])
+        );
+        for (map_id, cell_id) in [(map_1, cell_1), (map_2, cell_2)] {
+            let cells = cell_q.on_map(map_id);
+            assert_eq!(cells.get_at([1, 1]), Some(cell_id));
+            assert_eq!(
+                cells.iter_in([-4, -4], [4, 4]).collect::<Vec<_>>(),
+                vec![cell_id]
+            );
+
+            let chunk_id = chunk_q.on_map(map_id).get_at([0, 0]).unwrap();
+            let chunk = world.get::<Chunk>(chunk_id).unwrap();
+            assert_eq!(chunk.cells.iter().flatten().collect::<Vec<_>>(), [&cell_id]);
+        }
+    }
+}
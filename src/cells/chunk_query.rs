@@ -7,7 +7,7 @@ use bevy::{
         query::{ReadOnlyWorldQuery, WorldQuery},
         system::SystemParam,
     },
-    prelude::Query,
+    prelude::{Entity, Query},
 };
 
 use super::{CellMap, CellMapLabel, Chunk, InMap};
@@ -59,6 +59,19 @@ where
     Q: WorldQuery + 'static,
     F: ReadOnlyWorldQuery + 'static,
 {
+    /// Get's the entity of the chunk at the given coordinate.
+    /// If no map entity is given, the single map for the label is used.
+    #[inline]
+    fn get_chunk_id(&self, map_id: Option<Entity>, cell_c: [isize; N]) -> Option<Entity> {
+        let map = if let Some(map_id) = map_id {
+            self.map_q.get(map_id).ok()?
+        } else {
+            self.map_q.get_single().ok()?
+        };
+        let chunk_c = calculate_chunk_coordinate(cell_c, map.config.chunk_size());
+        map.chunks.get(&chunk_c.into()).cloned()
+    }
+
     /// Get's the readonly query item for the given cell.
     /// # Note
     /// Coordinates are for these calls are in chunk coordinates.
@@ -67,11 +80,9 @@ where
         &self,
         cell_c: [isize; N],
     ) -> Option<<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>> {
-        let map = self.map_q.get_single().ok()?;
-        let chunk_c = calculate_chunk_coordinate(cell_c, L::CHUNK_SIZE);
-        let chunk_e = map.chunks.get(&chunk_c.into())?;
+        let chunk_e = self.get_chunk_id(None, cell_c)?;
 
-        self.chunk_q.get(*chunk_e).ok()
+        self.chunk_q.get(chunk_e).ok()
     }
 
     /// Get's the query item for the given cell.
@@ -79,11 +90,9 @@ where
     /// Coordinates are for these calls are in chunk coordinates.
     #[inline]
     pub fn get_at_mut(&mut self, cell_c: [isize; N]) -> Option<<Q as WorldQuery>::Item<'_>> {
-        let map = self.map_q.get_single().ok()?;
-        let chunk_c = calculate_chunk_coordinate(cell_c, L::CHUNK_SIZE);
-        let chunk_e = map.chunks.get(&chunk_c.into())?;
+        let chunk_e = self.get_chunk_id(None, cell_c)?;
 
-        self.chunk_q.get_mut(*chunk_e).ok()
+        self.chunk_q.get_mut(chunk_e).ok()
     }
 
     /// Get's the query item for the given chunk.
@@ -96,11 +105,9 @@ where
         &self,
         cell_c: [isize; N],
     ) -> Option<<Q as WorldQuery>::Item<'_>> {
-        let map = self.map_q.get_single().ok()?;
-        let chunk_c = calculate_chunk_coordinate(cell_c, L::CHUNK_SIZE);
-        let chunk_e = map.chunks.get(&chunk_c.into())?;
+        let chunk_e = self.get_chunk_id(None, cell_c)?;
 
-        self.chunk_q.get_unchecked(*chunk_e).ok()
+        self.chunk_q.get_unchecked(chunk_e).ok()
     }
 
     /// Iterate over all the chunks in a given space, starting at `corner_1`
@@ -113,7 +120,7 @@ where
         corner_1: [isize; N],
         corner_2: [isize; N],
     ) -> ChunkQueryIter<'_, 's, L, Q, F, N> {
-        unsafe { ChunkQueryIter::new(self, corner_1, corner_2) }
+        unsafe { ChunkQueryIter::new(self, None, corner_1, corner_2) }
     }
 
     /// Iterate over all the chunks in a given space, starting at `corner_1`
@@ -126,7 +133,7 @@ where
         corner_1: [isize; N],
         corner_2: [isize; N],
     ) -> ChunkQueryIterMut<'_, 's, L, Q, F, N> {
-        unsafe { ChunkQueryIterMut::new(self, corner_1, corner_2) }
+        unsafe { ChunkQueryIterMut::new(self, None, corner_1, corner_2) }
     }
 
     #[inline]
@@ -138,6 +145,153 @@ where
             map_q: self.map_q.to_readonly(),
         }
     }
+
+    /// Get's a view of this query that resolves chunks on the given map entity.
+    /// # Note
+    /// Use this when multiple maps share the same [CellMapLabel].
+    #[inline]
+    pub fn on_map(&self, map_id: Entity) -> ChunkQueryOnMap<'_, 'w, 's, L, Q, F, N> {
+        ChunkQueryOnMap {
+            chunk_q: self,
+            map_id,
+        }
+    }
+
+    /// Get's a mutable view of this query that resolves chunks on the given map entity.
+    /// # Note
+    /// Use this when multiple maps share the same [CellMapLabel].
+    #[inline]
+    pub fn on_map_mut(&mut self, map_id: Entity) -> ChunkQueryOnMapMut<'_, 'w, 's, L, Q, F, N> {
+        ChunkQueryOnMapMut {
+            chunk_q: self,
+            map_id,
+        }
+    }
+}
+
+/// A [ChunkQuery] that resolves chunks on a specific map entity instead
+/// of the single map for its [CellMapLabel].
+pub struct ChunkQueryOnMap<'a, 'w, 's, L, Q, F, const N: usize>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    chunk_q: &'a ChunkQuery<'w, 's, L, Q, F, N>,
+    map_id: Entity,
+}
+
+impl<'a, 'w, 's, L, Q, F, const N: usize> ChunkQueryOnMap<'a, 'w, 's, L, Q, F, N>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    /// Get's the map entity this query resolves chunks on.
+    #[inline]
+    pub fn map_id(&self) -> Entity {
+        self.map_id
+    }
+
+    /// Get's the readonly query item for the given cell.
+    /// # Note
+    /// Coordinates are for these calls are in chunk coordinates.
+    #[inline]
+    pub fn get_at(
+        &self,
+        cell_c: [isize; N],
+    ) -> Option<<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>> {
+        let chunk_e = self.chunk_q.get_chunk_id(Some(self.map_id), cell_c)?;
+
+        self.chunk_q.chunk_q.get(chunk_e).ok()
+    }
+
+    /// Iterate over all the chunks in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`
+    /// # Note
+    /// Coordinates are for these calls are in chunk coordinates.
+    #[inline]
+    pub fn iter_in(
+        &self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> ChunkQueryIter<'_, 's, L, Q, F, N> {
+        unsafe { ChunkQueryIter::new(self.chunk_q, Some(self.map_id), corner_1, corner_2) }
+    }
+}
+
+/// A mutable [ChunkQuery] that resolves chunks on a specific map entity instead
+/// of the single map for its [CellMapLabel].
+pub struct ChunkQueryOnMapMut<'a, 'w, 's, L, Q, F, const N: usize>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    chunk_q: &'a mut ChunkQuery<'w, 's, L, Q, F, N>,
+    map_id: Entity,
+}
+
+impl<'a, 'w, 's, L, Q, F, const N: usize> ChunkQueryOnMapMut<'a, 'w, 's, L, Q, F, N>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    /// Get's the map entity this query resolves chunks on.
+    #[inline]
+    pub fn map_id(&self) -> Entity {
+        self.map_id
+    }
+
+    /// Get's the readonly query item for the given cell.
+    /// # Note
+    /// Coordinates are for these calls are in chunk coordinates.
+    #[inline]
+    pub fn get_at(
+        &self,
+        cell_c: [isize; N],
+    ) -> Option<<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'_>> {
+        let chunk_e = self.chunk_q.get_chunk_id(Some(self.map_id), cell_c)?;
+
+        self.chunk_q.chunk_q.get(chunk_e).ok()
+    }
+
+    /// Iterate over all the chunks in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`
+    /// # Note
+    /// Coordinates are for these calls are in chunk coordinates.
+    #[inline]
+    pub fn iter_in(
+        &self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> ChunkQueryIter<'_, 's, L, Q, F, N> {
+        unsafe { ChunkQueryIter::new(self.chunk_q, Some(self.map_id), corner_1, corner_2) }
+    }
+
+    /// Get's the query item for the given cell.
+    /// # Note
+    /// Coordinates are for these calls are in chunk coordinates.
+    #[inline]
+    pub fn get_at_mut(&mut self, cell_c: [isize; N]) -> Option<<Q as WorldQuery>::Item<'_>> {
+        let chunk_e = self.chunk_q.get_chunk_id(Some(self.map_id), cell_c)?;
+
+        self.chunk_q.chunk_q.get_mut(chunk_e).ok()
+    }
+
+    /// Iterate over all the chunks in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`.
+    /// # Note
+    /// Coordinates are for these calls are in chunk coordinates.
+    #[inline]
+    pub fn iter_in_mut(
+        &mut self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> ChunkQueryIterMut<'_, 's, L, Q, F, N> {
+        unsafe { ChunkQueryIterMut::new(self.chunk_q, Some(self.map_id), corner_1, corner_2) }
+    }
 }
 
 pub struct ChunkQueryIter<'w, 's, L, Q, F, const N: usize>
@@ -148,6 +302,7 @@ where
 {
     coord_iter: CoordIterator<N>,
     cell_q: &'w ChunkQuery<'w, 's, L, Q, F, N>,
+    map_id: Option<Entity>,
 }
 
 impl<'w, 's, L, Q, F, const N: usize> ChunkQueryIter<'w, 's, L, Q, F, N>
@@ -162,11 +317,13 @@ where
     /// borrowed mutabley.
     unsafe fn new(
         cell_q: &'w ChunkQuery<'w, 's, L, Q, F, N>,
+        map_id: Option<Entity>,
         corner_1: [isize; N],
         corner_2: [isize; N],
     ) -> Self {
         Self {
             cell_q,
+            map_id,
             coord_iter: CoordIterator::new(corner_1, corner_2),
         }
     }
@@ -185,7 +342,10 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         while let Some(target) = self.coord_iter.next() {
             // This fixes some lifetime issue that I'm not sure I understand quite yet, will do testing
-            let cell = self.cell_q.get_at(target);
+            let cell = self
+                .cell_q
+                .get_chunk_id(self.map_id, target)
+                .and_then(|chunk_e| self.cell_q.chunk_q.get(chunk_e).ok());
             if cell.is_some() {
                 return cell;
             }
@@ -212,6 +372,7 @@ where
 {
     coord_iter: CoordIterator<N>,
     cell_q: &'w ChunkQuery<'w, 's, L, Q, F, N>,
+    map_id: Option<Entity>,
 }
 
 impl<'w, 's, L, Q, F, const N: usize> ChunkQueryIterMut<'w, 's, L, Q, F, N>
@@ -226,11 +387,13 @@ where
     /// borrowed mutabley.
     unsafe fn new(
         cell_q: &'w ChunkQuery<'w, 's, L, Q, F, N>,
+        map_id: Option<Entity>,
         corner_1: [isize; N],
         corner_2: [isize; N],
     ) -> Self {
         Self {
             cell_q,
+            map_id,
             coord_iter: CoordIterator::new(corner_1, corner_2),
         }
     }
@@ -249,7 +412,10 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         while let Some(target) = self.coord_iter.next() {
             // This fixes some lifetime issue that I'm not sure I understand quite yet, will do testing
-            let cell = unsafe { self.cell_q.get_at_unchecked(target) };
+            let cell = self
+                .cell_q
+                .get_chunk_id(self.map_id, target)
+                .and_then(|chunk_e| unsafe { self.cell_q.chunk_q.get_unchecked(chunk_e).ok() });
             if cell.is_some() {
                 return cell;
             }
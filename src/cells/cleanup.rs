@@ -0,0 +1,235 @@
+use std::{
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+};
+
+use bevy::{
+    app::{App, Last, Plugin},
+    ecs::{
+        entity::{Entities, Entity},
+        event::Events,
+        query::Changed,
+        removal_detection::RemovedComponents,
+        system::{Local, Query, ResMut},
+    },
+};
+
+use super::{
+    coords::{calculate_cell_coordinate, calculate_cell_index, calculate_chunk_coordinate},
+    events::CellDespawned,
+    CellCoord, CellMap, CellMapLabel, Chunk, ChunkCoord,
+};
+
+/// Keeps cell maps for a label from holding on to cells that were despawned directly,
+/// for example with `commands.entity(cell).despawn()`.
+/// # Note
+/// Chunk slots are cleared at the end of the frame the cell was despawned in,
+/// and a [CellDespawned] event is sent for each cleared cell if the events are registered.
+/// The plugin remembers the coordinate of every cell so it only has to look in the chunk a cell was in.
+pub struct CellCleanupPlugin<L, const N: usize = 2>(PhantomData<L>);
+
+impl<L, const N: usize> Default for CellCleanupPlugin<L, N> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<L, const N: usize> Plugin for CellCleanupPlugin<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.add_systems(Last, clean_despawned_cells::<L, N>);
+    }
+}
+
+fn clean_despawned_cells<L, const N: usize>(
+    mut removed: RemovedComponents<CellCoord<N>>,
+    entities: &Entities,
+    moved: Query<(Entity, &CellCoord<N>), Changed<CellCoord<N>>>,
+    mut cell_cs: Local<HashMap<Entity, [isize; N]>>,
+    maps: Query<(Entity, &CellMap<L, N>)>,
+    mut chunks: Query<(&ChunkCoord<N>, &mut Chunk)>,
+    mut events: Option<ResMut<Events<CellDespawned<L, N>>>>,
+) where
+    L: CellMapLabel + 'static,
+{
+    // Cells that lose their coordinate but are still alive have been taken out of the map properly
+    let mut despawned = Vec::new();
+    let mut untracked = HashSet::new();
+    for cell_id in removed.read() {
+        let cell_c = cell_cs.remove(&cell_id);
+        if entities.contains(cell_id) {
+            continue;
+        }
+        match cell_c {
+            Some(cell_c) => despawned.push((cell_id, cell_c)),
+            None => {
+                untracked.insert(cell_id);
+            }
+        }
+    }
+    cell_cs.extend(moved.iter().map(|(cell_id, cell_c)| (cell_id, **cell_c)));
+
+    let mut found = HashSet::new();
+    for (map_id, map) in maps.iter() {
+        let chunk_size = map.config.chunk_size();
+        for (cell_id, cell_c) in despawned.iter().copied() {
+            let chunk_c = calculate_chunk_coordinate(cell_c, chunk_size);
+            let Some((_, mut chunk)) = map
+                .chunks
+                .get(&chunk_c.into())
+                .and_then(|chunk_id| chunks.get_mut(*chunk_id).ok())
+            else {
+                continue;
+            };
+            // Another map for the label may have a cell at the same coordinate
+            let cell_i = calculate_cell_index(cell_c, chunk_size);
+            if chunk.cells.get(cell_i) != Some(&Some(cell_id)) {
+                continue;
+            }
+            chunk.cells[cell_i] = None;
+            found.insert(cell_id);
+            if let Some(events) = events.as_mut() {
+                events.send(CellDespawned {
+                    map_id,
+                    cell_c,
+                    entity: cell_id,
+                    label: PhantomData,
+                });
+            }
+        }
+    }
+
+    // Cells despawned before they were ever seen here, or moved in the same frame they were
+    // despawned in, aren't where they were last seen and have to be searched for
+    untracked.extend(
+        despawned
+            .into_iter()
+            .map(|(cell_id, _)| cell_id)
+            .filter(|cell_id| !found.contains(cell_id)),
+    );
+    if untracked.is_empty() {
+        return;
+    }
+    for (map_id, map) in maps.iter() {
+        for chunk_id in map.chunks.values() {
+            let Ok((chunk_c, mut chunk)) = chunks.get_mut(*chunk_id) else {
+                continue;
+            };
+            // Only touch chunks that hold despawned cells, to keep change detection accurate
+            let stale = chunk
+                .cells
+                .iter()
+                .enumerate()
+                .filter_map(|(cell_i, cell)| Some((cell_i, (*cell)?)))
+                .filter(|(_, cell_id)| untracked.contains(cell_id))
+                .collect::<Vec<_>>();
+            for (cell_i, cell_id) in stale {
+                chunk.cells[cell_i] = None;
+                if let Some(events) = events.as_mut() {
+                    events.send(CellDespawned {
+                        map_id,
+                        cell_c: calculate_cell_coordinate(
+                            **chunk_c,
+                            cell_i,
+                            map.config.chunk_size(),
+                        ),
+                        entity: cell_id,
+                        label: PhantomData,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::event::ManualEventReader;
+
+    use super::*;
+    use crate::{
+        cells::{
+            commands::{insert_cell, move_cell},
+            events::CellEventsPlugin,
+        },
+        CellsPlugin,
+    };
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 16;
+    }
+
+    #[test]
+    fn clears_despawned_cells() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellsPlugin,
+            CellEventsPlugin::<TestLayer>::default(),
+            CellCleanupPlugin::<TestLayer>::default(),
+        ));
+
+        let tracked = app.world.spawn_empty().id();
+        let kept = app.world.spawn_empty().id();
+        insert_cell::<TestLayer, 2>(&mut app.world, None, [-16, 0], tracked).unwrap();
+        insert_cell::<TestLayer, 2>(&mut app.world, None, [0, 0], kept).unwrap();
+        app.update();
+
+        // This cell is despawned before the cleanup has seen it
+        let cell = app.world.spawn_empty().id();
+        insert_cell::<TestLayer, 2>(&mut app.world, None, [-3, 5], cell).unwrap();
+        app.world.despawn(cell);
+        app.world.despawn(tracked);
+        app.update();
+
+        let world = &mut app.world;
+        let cells = world
+            .query::<&Chunk>()
+            .iter(world)
+            .flat_map(|chunk| chunk.cells.iter().flatten().copied())
+            .collect::<Vec<_>>();
+        assert_eq!(cells, vec![kept]);
+        let mut events = ManualEventReader::<CellDespawned<TestLayer>>::default()
+            .read(world.resource::<Events<CellDespawned<TestLayer>>>())
+            .map(|event| (event.cell_c, event.entity))
+            .collect::<Vec<_>>();
+        events.sort();
+        assert_eq!(events, vec![([-16, 0], tracked), ([-3, 5], cell)]);
+    }
+
+    #[test]
+    fn clears_cells_moved_then_despawned() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellsPlugin,
+            CellEventsPlugin::<TestLayer>::default(),
+            CellCleanupPlugin::<TestLayer>::default(),
+        ));
+
+        let cell = app.world.spawn_empty().id();
+        insert_cell::<TestLayer, 2>(&mut app.world, None, [1, 1], cell).unwrap();
+        app.update();
+
+        move_cell::<TestLayer, 2>(&mut app.world, None, [1, 1], [40, -7]).unwrap();
+        app.world.despawn(cell);
+        app.update();
+
+        let world = &mut app.world;
+        assert_eq!(
+            world
+                .query::<&Chunk>()
+                .iter(world)
+                .flat_map(|chunk| chunk.cells.iter().flatten())
+                .count(),
+            0
+        );
+        let events = ManualEventReader::<CellDespawned<TestLayer>>::default()
+            .read(world.resource::<Events<CellDespawned<TestLayer>>>())
+            .map(|event| (event.cell_c, event.entity))
+            .collect::<Vec<_>>();
+        assert_eq!(events, vec![([40, -7], cell)]);
+    }
+}
@@ -7,33 +7,47 @@ use std::{
 
 use super::{
     coords::{calculate_cell_index, calculate_chunk_coordinate},
-    CellCoord, CellIndex, CellMap, CellMapLabel, Chunk, ChunkCoord, InChunk, InMap,
+    data::HasCellData,
+    error::CellError,
+    events::{CellCommandFailed, CellDespawned, CellMoved, CellReplaced, CellSpawned},
+    generation::{ChunkGenerator, GenerateChunk},
+    CellCoord, CellIndex, CellMap, CellMapConfig, CellMapLabel, Chunk, ChunkCoord, InChunk, InMap,
+    InsertPolicy,
 };
 use aery::{
     edges::{CheckedDespawn, Unset, Withdraw},
     prelude::Set,
 };
 use bevy::{
-    ecs::system::{Command, EntityCommands},
+    ecs::{
+        event::Events,
+        system::{Command, EntityCommands},
+    },
+    log::warn,
     prelude::{Bundle, Commands, Entity, With, World},
-    utils::{hashbrown::hash_map::Entry, HashMap},
+    utils::{hashbrown::hash_map::Entry, HashMap, HashSet},
 };
 
 mod cell_batch;
+mod cell_data;
 mod cell_single;
 mod chunk_batch;
 mod chunk_single;
 mod map;
+mod world;
 
 use cell_batch::*;
+use cell_data::*;
 use cell_single::*;
 use chunk_batch::*;
 use chunk_single::*;
 use map::*;
+pub use world::*;
 
 /// Applies commands to a specific cell map.
 pub struct CellCommands<'a, 'w, 's, L, const N: usize> {
     commands: &'a mut Commands<'w, 's>,
+    map_id: Option<Entity>,
     phantom: PhantomData<L>,
 }
 
@@ -62,6 +76,16 @@ pub trait CellCommandExt<'w, 's> {
     fn cells<'a, L, const N: usize>(&'a mut self) -> CellCommands<'a, 'w, 's, L, N>
     where
         L: CellMapLabel + 'static;
+
+    /// Gets the [CellCommands] to apply commands to a specific cell map entity.
+    /// # Note
+    /// Use this when multiple maps share the same [CellMapLabel].
+    fn cells_on<'a, L, const N: usize>(
+        &'a mut self,
+        map_id: Entity,
+    ) -> CellCommands<'a, 'w, 's, L, N>
+    where
+        L: CellMapLabel + 'static;
 }
 
 impl<'w, 's> CellCommandExt<'w, 's> for Commands<'w, 's> {
@@ -71,6 +95,18 @@ impl<'w, 's> CellCommandExt<'w, 's> for Commands<'w, 's> {
     {
         CellCommands {
             commands: self,
+            map_id: None,
+            phantom: PhantomData,
+        }
+    }
+
+    fn cells_on<L, const N: usize>(&mut self, map_id: Entity) -> CellCommands<'_, 'w, 's, L, N>
+    where
+        L: CellMapLabel + 'static,
+    {
+        CellCommands {
+            commands: self,
+            map_id: Some(map_id),
             phantom: PhantomData,
         }
     }
@@ -80,16 +116,46 @@ impl<'a, 'w, 's, L, const N: usize> CellCommands<'a, 'w, 's, L, N>
 where
     L: CellMapLabel + 'static,
 {
-    /// Spawns a cell and returns a handle to the underlying entity.
-    /// This will despawn any cell that already exists in this coordinate
+    /// Spawns a cell and returns a handle to the underlying entity,
+    /// following the [InsertPolicy] of the map if the coordinate already has a cell.
+    /// # Note
+    /// Outside the bounds of the map the cell is clamped or despawned, following its [BoundsPolicy](super::BoundsPolicy).
     pub fn spawn_cell<T>(&mut self, cell_c: [isize; N], bundle: T) -> EntityCommands<'w, 's, '_>
     where
         T: Bundle + 'static,
     {
         let cell_id = self.spawn(bundle).id();
+        let map_id = self.map_id;
         self.add(SpawnCell::<L, N> {
+            map_id,
             cell_c,
             cell_id,
+            policy: None,
+            label: std::marker::PhantomData,
+        });
+        self.entity(cell_id)
+    }
+
+    /// Spawns a cell and returns a handle to the underlying entity,
+    /// following the given policy if the coordinate already has a cell.
+    /// # Note
+    /// With [InsertPolicy::Reject] the returned entity may be despawned when the command is applied.
+    pub fn spawn_cell_with_policy<T>(
+        &mut self,
+        cell_c: [isize; N],
+        bundle: T,
+        policy: InsertPolicy,
+    ) -> EntityCommands<'w, 's, '_>
+    where
+        T: Bundle + 'static,
+    {
+        let cell_id = self.spawn(bundle).id();
+        let map_id = self.map_id;
+        self.add(SpawnCell::<L, N> {
+            map_id,
+            cell_c,
+            cell_id,
+            policy: Some(policy),
             label: std::marker::PhantomData,
         });
         self.entity(cell_id)
@@ -97,13 +163,18 @@ where
 
     /// Spawns cells from the given iterator using the given function.
     /// This will despawn any cell that already exists in this coordinate
+    /// # Note
+    /// Cells outside the bounds of the map are clamped or skipped, following its [BoundsPolicy](super::BoundsPolicy).
+    /// Skipped cells, and clamped cells that would land on another cell of the batch, aren't spawned
+    /// and are reported with a [CellCommandFailed] event.
     pub fn spawn_cell_batch<F, B, IC>(&mut self, cell_cs: IC, bundle_f: F)
     where
         F: Fn([isize; N]) -> B + Send + 'static,
         B: Bundle + Send + 'static,
         IC: IntoIterator<Item = [isize; N]> + Send + 'static,
     {
-        self.add(SpawnCellBatch::<L, F, B, IC, N> {
+        self.commands.add(SpawnCellBatch::<L, F, B, IC, N> {
+            map_id: self.map_id,
             cell_cs,
             bundle_f,
             label: std::marker::PhantomData,
@@ -112,8 +183,21 @@ where
 
     /// Despawns a cell.
     pub fn despawn_cell(&mut self, cell_c: [isize; N]) -> &mut Self {
-        self.add(DespawnCell::<L, N> {
+        self.commands.add(DespawnCell::<L, N> {
+            map_id: self.map_id,
+            cell_c,
+            fallible: false,
+            label: PhantomData,
+        });
+        self
+    }
+
+    /// Despawns a cell, sending a [CellCommandFailed] event if there is no cell to despawn.
+    pub fn try_despawn_cell(&mut self, cell_c: [isize; N]) -> &mut Self {
+        self.commands.add(DespawnCell::<L, N> {
+            map_id: self.map_id,
             cell_c,
+            fallible: true,
             label: PhantomData,
         });
         self
@@ -124,17 +208,35 @@ where
     where
         IC: IntoIterator<Item = [isize; N]> + Send + 'static,
     {
-        self.add(DespawnCellBatch::<L, IC, N> {
+        self.commands.add(DespawnCellBatch::<L, IC, N> {
+            map_id: self.map_id,
             cell_cs,
             label: std::marker::PhantomData,
         });
     }
 
     /// Moves a cell from one coordinate to another, overwriting and despawning any cell in the new coordinate.
+    /// # Note
+    /// Moves outside the bounds of the map are clamped or ignored, following its [BoundsPolicy](super::BoundsPolicy).
     pub fn move_cell(&mut self, old_c: [isize; N], new_c: [isize; N]) -> &mut Self {
-        self.add(MoveCell::<L, N> {
+        self.commands.add(MoveCell::<L, N> {
+            map_id: self.map_id,
+            old_c,
+            new_c,
+            fallible: false,
+            label: PhantomData,
+        });
+        self
+    }
+
+    /// Moves a cell from one coordinate to another, sending a [CellCommandFailed] event
+    /// if there is no cell to move or the new coordinate already has a cell or is out of bounds.
+    pub fn try_move_cell(&mut self, old_c: [isize; N], new_c: [isize; N]) -> &mut Self {
+        self.commands.add(MoveCell::<L, N> {
+            map_id: self.map_id,
             old_c,
             new_c,
+            fallible: true,
             label: PhantomData,
         });
         self
@@ -146,7 +248,8 @@ where
     where
         IC: IntoIterator<Item = ([isize; N], [isize; N])> + Send + 'static,
     {
-        self.add(MoveCellBatch::<L, IC, N> {
+        self.commands.add(MoveCellBatch::<L, IC, N> {
+            map_id: self.map_id,
             cell_cs,
             label: std::marker::PhantomData,
         });
@@ -154,9 +257,23 @@ where
 
     /// Swaps two cells if both exist, or just moves one cell if the other doesn't exist.
     pub fn swap_cells(&mut self, cell_c_1: [isize; N], cell_c_2: [isize; N]) -> &mut Self {
-        self.add(SwapCell::<L, N> {
+        self.commands.add(SwapCell::<L, N> {
+            map_id: self.map_id,
             cell_c_1,
             cell_c_2,
+            fallible: false,
+            label: PhantomData,
+        });
+        self
+    }
+
+    /// Swaps two cells, sending a [CellCommandFailed] event if neither cell exists.
+    pub fn try_swap_cells(&mut self, cell_c_1: [isize; N], cell_c_2: [isize; N]) -> &mut Self {
+        self.commands.add(SwapCell::<L, N> {
+            map_id: self.map_id,
+            cell_c_1,
+            cell_c_2,
+            fallible: true,
             label: PhantomData,
         });
         self
@@ -167,19 +284,48 @@ where
     where
         IC: IntoIterator<Item = ([isize; N], [isize; N])> + Send + 'static,
     {
-        self.add(SwapCellBatch::<L, IC, N> {
+        self.commands.add(SwapCellBatch::<L, IC, N> {
+            map_id: self.map_id,
             cell_cs,
             label: std::marker::PhantomData,
         });
     }
 
+    /// Sets the value of a cell in the [CellData](super::data::CellData) layer of type `T`,
+    /// adding the layer to the chunk if it doesn't have one.
+    /// # Note
+    /// This will spawn the map and the chunk if they don't exist.
+    pub fn set_cell_data<T>(&mut self, cell_c: [isize; N], value: T) -> &mut Self
+    where
+        T: Default + Clone + Send + Sync + 'static,
+    {
+        self.set_cell_data_batch([(cell_c, value)]);
+        self
+    }
+
+    /// Sets the values of cells from the given iterator in the [CellData](super::data::CellData) layer of type `T`,
+    /// adding the layer to chunks that don't have one.
+    pub fn set_cell_data_batch<T, IC>(&mut self, values: IC)
+    where
+        T: Default + Clone + Send + Sync + 'static,
+        IC: IntoIterator<Item = ([isize; N], T)> + Send + 'static,
+    {
+        self.commands.add(SetCellData::<L, T, IC, N> {
+            map_id: self.map_id,
+            values,
+            label: PhantomData,
+        });
+    }
+
     /// Manually spawn a chunk entity, note that this will overwrite and despawn existing chunks at this location.
     pub fn spawn_chunk<T>(&mut self, chunk_c: [isize; N], bundle: T) -> EntityCommands<'w, 's, '_>
     where
         T: Bundle + 'static,
     {
         let chunk_id = self.spawn(bundle).id();
+        let map_id = self.map_id;
         self.add(SpawnChunk::<L, N> {
+            map_id,
             chunk_c,
             chunk_id,
             label: std::marker::PhantomData,
@@ -195,7 +341,8 @@ where
         B: Bundle + Send + 'static,
         IC: IntoIterator<Item = [isize; N]> + Send + 'static,
     {
-        self.add(SpawnChunkBatch::<L, F, B, IC, N> {
+        self.commands.add(SpawnChunkBatch::<L, F, B, IC, N> {
+            map_id: self.map_id,
             chunk_cs,
             bundle_f,
             label: std::marker::PhantomData,
@@ -204,8 +351,21 @@ where
 
     /// Recursively despawn a chunk and all it's cells.
     pub fn despawn_chunk(&mut self, chunk_c: [isize; N]) -> &mut Self {
-        self.add(DespawnChunk::<L, N> {
+        self.commands.add(DespawnChunk::<L, N> {
+            map_id: self.map_id,
+            chunk_c,
+            fallible: false,
+            label: std::marker::PhantomData,
+        });
+        self
+    }
+
+    /// Recursively despawn a chunk and all it's cells, sending a [CellCommandFailed] event if the chunk doesn't exist.
+    pub fn try_despawn_chunk(&mut self, chunk_c: [isize; N]) -> &mut Self {
+        self.commands.add(DespawnChunk::<L, N> {
+            map_id: self.map_id,
             chunk_c,
+            fallible: true,
             label: std::marker::PhantomData,
         });
         self
@@ -216,113 +376,259 @@ where
     where
         IC: IntoIterator<Item = [isize; N]> + Send + 'static,
     {
-        self.add(DespawnChunkBatch::<L, IC, N> {
+        self.commands.add(DespawnChunkBatch::<L, IC, N> {
+            map_id: self.map_id,
             chunk_cs,
             label: std::marker::PhantomData,
         });
     }
 
+    /// Generates a chunk with the [ChunkGenerator] resource `G`, if the chunk hasn't been generated yet.
+    pub fn generate_chunk<G>(&mut self, chunk_c: [isize; N]) -> &mut Self
+    where
+        G: ChunkGenerator<L, N>,
+    {
+        self.commands.add(GenerateChunk::<L, G, N> {
+            map_id: self.map_id,
+            chunk_c,
+            label: PhantomData,
+        });
+        self
+    }
+
     /// Recursively despawns a map and all it's chunks and cells.
     pub fn despawn_map(&mut self) -> &mut Self {
-        self.add(DespawnMap::<L, N> { label: PhantomData });
+        self.commands.add(DespawnMap::<L, N> {
+            map_id: self.map_id,
+            label: PhantomData,
+        });
         self
     }
 }
 
-/// Spawns a chunk in the world if needed, inserts the info into the map, and returns
-/// and id for reinsertion
+/// Gets the chunk at a chunk coordinate, or spawns a new chunk and adds it to the map.
+/// # Note
+/// The map must exist, see [spawn_or_find_map].
 #[inline]
-fn spawn_or_remove_chunk<L, const N: usize>(
+fn spawn_or_find_chunk<L, const N: usize>(
     world: &mut World,
-    map: &mut CellMap<L, N>,
     map_id: Entity,
     chunk_c: [isize; N],
-) -> (Entity, Chunk)
+) -> Entity
 where
     L: CellMapLabel + Send + 'static,
 {
-    if let Some(chunk_info) = remove_chunk::<L, N>(world, map, chunk_c) {
-        chunk_info
+    if let Some(chunk_id) = find_chunk::<L, N>(world, map_id, chunk_c) {
+        chunk_id
     } else {
-        let chunk_id = world.spawn(ChunkCoord::from(chunk_c)).id();
-        map.chunks.insert(chunk_c.into(), chunk_id);
+        let chunk_size = map_config::<L, N>(world, map_id).chunk_size();
+        let chunk_id = world
+            .spawn((
+                ChunkCoord::from(chunk_c),
+                Chunk::new(chunk_size.iter().product()),
+            ))
+            .id();
+        world
+            .get_mut::<CellMap<L, N>>(map_id)
+            .unwrap()
+            .chunks
+            .insert(chunk_c.into(), chunk_id);
         Set::<InMap<L, N>>::new(chunk_id, map_id).apply(world);
-        (chunk_id, Chunk::new(L::CHUNK_SIZE.pow(N as u32)))
+        chunk_id
     }
 }
 
-/// Removes a chunk from the world if it exists, and returns the info to reinsert it.
+/// Gets the chunk at a chunk coordinate if it exists.
 #[inline]
-fn remove_chunk<L, const N: usize>(
-    world: &mut World,
-    map: &mut CellMap<L, N>,
+fn find_chunk<L, const N: usize>(
+    world: &World,
+    map_id: Entity,
     chunk_c: [isize; N],
-) -> Option<(Entity, Chunk)>
+) -> Option<Entity>
 where
     L: CellMapLabel + Send + 'static,
 {
-    map.chunks
+    world
+        .get::<CellMap<L, N>>(map_id)?
+        .chunks
         .get(&chunk_c.into())
-        .cloned()
-        .and_then(|chunk_id| world.get_entity_mut(chunk_id))
-        .map(|mut chunk_e| (chunk_e.id(), chunk_e.take::<Chunk>().unwrap()))
+        .copied()
+        .filter(|chunk_id| world.get::<Chunk>(*chunk_id).is_some())
 }
 
-/// Takes the map out of the world or spawns a new one and returns the entity id to return the map to.
+/// Gets the settings of a map, or the defaults from the label if the map doesn't exist.
 #[inline]
-fn spawn_or_remove_map<L, const N: usize>(world: &mut World) -> (Entity, CellMap<L, N>)
+fn map_config<L, const N: usize>(world: &World, map_id: Entity) -> CellMapConfig<L, N>
 where
     L: CellMapLabel + Send + 'static,
 {
-    let map_info = remove_map::<L, N>(world);
-    if let Some(map_info) = map_info {
-        map_info
+    world
+        .get::<CellMap<L, N>>(map_id)
+        .map(|map| map.config)
+        .unwrap_or_default()
+}
+
+/// Gets the map entity, or adds a new map to the given entity or a new one.
+/// If no map entity is given, the single map for the label is used.
+/// Returns [CellError::MapMissing] if the given entity doesn't exist.
+#[inline]
+pub(crate) fn spawn_or_find_map<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+) -> Result<Entity, CellError<N>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    if let Some(map_id) = find_map::<L, N>(world, map_id) {
+        Ok(map_id)
+    } else if let Some(map_id) = map_id {
+        world
+            .get_entity_mut(map_id)
+            .ok_or(CellError::MapMissing)?
+            .insert(CellMap::<L, N>::default());
+        Ok(map_id)
     } else {
-        (world.spawn_empty().id(), CellMap::<L, N>::default())
+        Ok(world.spawn(CellMap::<L, N>::default()).id())
     }
 }
 
-/// Takes the map out of the world if it exists.
-#[inline]
-fn remove_map<L, const N: usize>(world: &mut World) -> Option<(Entity, CellMap<L, N>)>
+/// Inserts a cell into the world, following the [InsertPolicy] of the map if the coordinate is occupied.
+/// If `map_id` is `None`, the single map for the label is used, and spawned if it doesn't exist.
+pub fn insert_cell<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    cell_c: [isize; N],
+    cell_id: Entity,
+) -> Result<InsertOutcome, CellError<N>>
 where
     L: CellMapLabel + Send + 'static,
 {
-    world
-        .query_filtered::<Entity, With<CellMap<L, N>>>()
-        .get_single_mut(world)
-        .ok()
-        .map(|map_id| {
-            (
-                map_id,
-                world
-                    .get_entity_mut(map_id)
-                    .unwrap()
-                    .take::<CellMap<L, N>>()
-                    .unwrap(),
-            )
-        })
+    let map_id = spawn_or_find_map::<L, N>(world, map_id)?;
+    let (cell_c, _, result) = insert_cell_inner::<L, N>(world, map_id, cell_c, cell_id, None);
+    send_insert_events::<L, N>(world, map_id, [(cell_c, cell_id, &result)]);
+    result
 }
 
-/// Inserts a cell into the world
-pub fn insert_cell<L, const N: usize>(world: &mut World, cell_c: [isize; N], cell_id: Entity)
+/// Inserts a cell into the world, following the given policy if the coordinate is occupied.
+/// If `map_id` is `None`, the single map for the label is used, and spawned if it doesn't exist.
+pub fn insert_cell_with_policy<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    cell_c: [isize; N],
+    cell_id: Entity,
+    policy: InsertPolicy,
+) -> Result<InsertOutcome, CellError<N>>
 where
     L: CellMapLabel + Send + 'static,
 {
-    // Take the map out and get the id to reinsert it
-    let (map_id, mut map) = spawn_or_remove_map::<L, N>(world);
+    let map_id = spawn_or_find_map::<L, N>(world, map_id)?;
+    let (cell_c, _, result) =
+        insert_cell_inner::<L, N>(world, map_id, cell_c, cell_id, Some(policy));
+    send_insert_events::<L, N>(world, map_id, [(cell_c, cell_id, &result)]);
+    result
+}
 
-    // Take the chunk out and get the id to reinsert it
-    let chunk_c = calculate_chunk_coordinate(cell_c, L::CHUNK_SIZE);
-    let (chunk_id, mut chunk) = spawn_or_remove_chunk::<L, N>(world, &mut map, map_id, chunk_c);
+/// What happened to a coordinate when a cell was inserted into it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InsertOutcome {
+    /// The coordinate was empty.
+    Inserted,
+    /// The coordinate had a cell, which was despawned.
+    Replaced(Entity),
+    /// The coordinate had a cell, which was removed from the map and is still alive.
+    Returned(Entity),
+    /// The coordinate had a cell, so the new cell was despawned instead.
+    Rejected(Entity),
+}
+
+impl InsertOutcome {
+    /// Returns true if the new cell is in the map.
+    #[inline]
+    pub fn is_inserted(&self) -> bool {
+        !matches!(self, InsertOutcome::Rejected(_))
+    }
+}
 
-    // Insert the tile
-    let cell_i = calculate_cell_index(cell_c, L::CHUNK_SIZE);
+/// Inserts a cell into an existing map without sending events,
+/// returning where the cell ended up along with what happened to the coordinate.
+/// If no policy is given, the policy of the map is used.
+#[inline]
+fn insert_cell_inner<L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    cell_c: [isize; N],
+    cell_id: Entity,
+    policy: Option<InsertPolicy>,
+) -> InsertResult<N>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let config = map_config::<L, N>(world, map_id);
+    let cell_c = match config.resolve(cell_c) {
+        Ok(cell_c) => cell_c,
+        Err(error) => return (cell_c, cell_id, Err(error)),
+    };
+    let chunk_c = calculate_chunk_coordinate(cell_c, config.chunk_size());
+    let chunk_id = spawn_or_find_chunk::<L, N>(world, map_id, chunk_c);
+
+    let cell_i = calculate_cell_index(cell_c, config.chunk_size());
+    let policy = policy.unwrap_or(config.insert_policy());
+    let result = place_cell::<L, N>(world, chunk_id, cell_c, cell_i, cell_id, policy);
+    (cell_c, cell_id, result)
+}
 
-    if let Some(cell) = chunk.cells.get_mut(cell_i) {
-        if let Some(old_cell_id) = cell.replace(cell_id) {
+/// Puts a cell into its slot in a chunk, following the policy if the slot is occupied.
+#[inline]
+fn place_cell<L, const N: usize>(
+    world: &mut World,
+    chunk_id: Entity,
+    cell_c: [isize; N],
+    cell_i: usize,
+    cell_id: Entity,
+    policy: InsertPolicy,
+) -> Result<InsertOutcome, CellError<N>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    // The old cell may have been despawned outside of the map
+    let occupant = world
+        .get::<Chunk>(chunk_id)
+        .unwrap()
+        .cells
+        .get(cell_i)
+        .copied()
+        .flatten()
+        .filter(|old_cell_id| world.get_entity(*old_cell_id).is_some());
+
+    let outcome = match (occupant, policy) {
+        (None, _) => InsertOutcome::Inserted,
+        (Some(old_cell_id), InsertPolicy::ReplaceDespawn) => {
             world.despawn(old_cell_id);
+            InsertOutcome::Replaced(old_cell_id)
         }
+        (Some(old_cell_id), InsertPolicy::ReplaceReturn) => {
+            world
+                .entity_mut(old_cell_id)
+                .remove::<(CellIndex, CellCoord<N>)>();
+            Unset::<InChunk<L, N>>::new(old_cell_id, chunk_id).apply(world);
+            InsertOutcome::Returned(old_cell_id)
+        }
+        (Some(_), InsertPolicy::Reject) => {
+            world.despawn(cell_id);
+            return Ok(InsertOutcome::Rejected(cell_id));
+        }
+        (Some(occupant), InsertPolicy::Error) => {
+            return Err(CellError::Occupied { cell_c, occupant });
+        }
+    };
+
+    if let Some(cell) = world
+        .get_mut::<Chunk>(chunk_id)
+        .unwrap()
+        .cells
+        .get_mut(cell_i)
+    {
+        *cell = Some(cell_id);
     }
 
     Set::<InChunk<L, N>>::new(cell_id, chunk_id).apply(world);
@@ -332,230 +638,441 @@ where
         .unwrap()
         .insert((CellIndex::from(cell_i), CellCoord::<N>::new(cell_c)));
 
-    world.get_entity_mut(chunk_id).unwrap().insert(chunk);
-    world.get_entity_mut(map_id).unwrap().insert(map);
+    Ok(outcome)
 }
 
 /// Take a cell from the world.
-pub fn take_cell<L, const N: usize>(world: &mut World, cell_c: [isize; N]) -> Option<Entity>
+/// If `map_id` is `None`, the single map for the label is used.
+pub fn take_cell<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    cell_c: [isize; N],
+) -> Option<Entity>
 where
     L: CellMapLabel + Send + 'static,
 {
-    // Get the map or return
-    let (map_id, mut map) = remove_map::<L, N>(world)?;
+    try_take_cell::<L, N>(world, map_id, cell_c).ok()
+}
 
-    // Get the old chunk or return
-    let chunk_c = calculate_chunk_coordinate(cell_c, L::CHUNK_SIZE);
-    let (chunk_id, mut chunk) =
-        if let Some(chunk_info) = remove_chunk::<L, N>(world, &mut map, chunk_c) {
-            chunk_info
-        } else {
-            world.get_entity_mut(map_id).unwrap().insert(map);
-            return None;
-        };
+/// Take a cell from the world, returning why it couldn't be taken if it fails.
+/// If `map_id` is `None`, the single map for the label is used.
+pub fn try_take_cell<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    cell_c: [isize; N],
+) -> Result<Entity, CellError<N>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let (map_id, cell_id) = take_cell_inner::<L, N>(world, map_id, cell_c)?;
+    despawn_empty_chunks::<L, N>(world, map_id, [cell_c]);
+    Ok(cell_id)
+}
+
+/// Takes a cell from the world without despawning its chunk if it's left empty,
+/// returning the id of the map and the cell.
+#[inline]
+fn take_cell_inner<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    cell_c: [isize; N],
+) -> Result<(Entity, Entity), CellError<N>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    // Get the map and chunk or return
+    let map_id = find_map::<L, N>(world, map_id).ok_or(CellError::MapMissing)?;
+    let chunk_size = map_config::<L, N>(world, map_id).chunk_size();
+    let chunk_c = calculate_chunk_coordinate(cell_c, chunk_size);
+    let chunk_id =
+        find_chunk::<L, N>(world, map_id, chunk_c).ok_or(CellError::ChunkMissing { chunk_c })?;
 
     // Remove the old entity or return if the old entity is already deleted
-    let cell_i = calculate_cell_index(cell_c, L::CHUNK_SIZE);
+    let cell_i = calculate_cell_index(cell_c, chunk_size);
+    let cell_id =
+        take_from_chunk::<L, N>(world, chunk_id, cell_i).ok_or(CellError::CellEmpty { cell_c })?;
+    Ok((map_id, cell_id))
+}
 
-    let cell = if let Some(mut cell_e) = chunk
+/// Empties a slot in a chunk, returning the cell that was in it if it still exists.
+#[inline]
+fn take_from_chunk<L, const N: usize>(
+    world: &mut World,
+    chunk_id: Entity,
+    cell_i: usize,
+) -> Option<Entity>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let mut cell_e = world
+        .get_mut::<Chunk>(chunk_id)?
         .cells
         .get_mut(cell_i)
         .and_then(|cell| cell.take())
-        .and_then(|cell_id| world.get_entity_mut(cell_id))
-    {
-        cell_e.remove::<(CellIndex, CellCoord)>();
-        let cell_id = cell_e.id();
-        Unset::<InChunk<L, N>>::new(cell_id, chunk_id).apply(world);
-        Some(cell_id)
-    } else {
-        None
-    };
-
-    world.get_entity_mut(chunk_id).unwrap().insert(chunk);
-    world.get_entity_mut(map_id).unwrap().insert(map);
-    cell
+        .and_then(|cell_id| world.get_entity_mut(cell_id))?;
+    cell_e.remove::<(CellIndex, CellCoord)>();
+    let cell_id = cell_e.id();
+    Unset::<InChunk<L, N>>::new(cell_id, chunk_id).apply(world);
+    Some(cell_id)
 }
 
-/// Inserts a list of entities into the corresponding cells of a given cell map
-pub fn insert_cell_batch<L, const N: usize>(
+/// Despawns the chunks holding the given cells and removes them from the map,
+/// if the label despawns empty chunks and they have no live cells or data layers left.
+#[inline]
+fn despawn_empty_chunks<L, const N: usize>(
     world: &mut World,
-    cells: impl IntoIterator<Item = ([isize; N], Entity)>,
+    map_id: Entity,
+    cell_cs: impl IntoIterator<Item = [isize; N]>,
 ) where
     L: CellMapLabel + Send + 'static,
 {
-    let chunked_cells = cells
+    if !L::DESPAWN_EMPTY_CHUNKS {
+        return;
+    }
+
+    let chunk_size = map_config::<L, N>(world, map_id).chunk_size();
+    let chunk_cs = cell_cs
         .into_iter()
-        .group_by(|(cell_c, _)| calculate_chunk_coordinate(*cell_c, L::CHUNK_SIZE));
+        .map(|cell_c| calculate_chunk_coordinate(cell_c, chunk_size))
+        .collect::<HashSet<[isize; N]>>();
 
-    // Remove the map, or spawn an entity to hold the map, then create an empty map
-    let (map_id, mut map) = spawn_or_remove_map::<L, N>(world);
+    for chunk_c in chunk_cs {
+        let Some(chunk_id) = find_chunk::<L, N>(world, map_id, chunk_c) else {
+            continue;
+        };
+        let chunk_e = world.entity(chunk_id);
+        let empty = !chunk_e.contains::<HasCellData>()
+            && chunk_e
+                .get::<Chunk>()
+                .unwrap()
+                .cells
+                .iter()
+                .flatten()
+                .all(|cell_id| world.get_entity(*cell_id).is_none());
+        if empty {
+            remove_chunk_entry::<L, N>(world, map_id, chunk_c);
+            CheckedDespawn(chunk_id).apply(world);
+        }
+    }
+}
 
-    // Get the chunks and entities from the map
-    let cells_with_chunk = Vec::from_iter(chunked_cells.into_iter().map(|(chunk_c, cells)| {
-        let (chunk_id, chunk) = spawn_or_remove_chunk::<L, N>(world, &mut map, map_id, chunk_c);
-        (chunk_id, chunk, cells)
-    }));
+/// Moves a cell from one coordinate to another, despawning any cell in the new coordinate.
+/// Returns the moved cell.
+/// If `map_id` is `None`, the single map for the label is used.
+pub fn move_cell<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    old_c: [isize; N],
+    new_c: [isize; N],
+) -> Result<Entity, CellError<N>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let map_id = find_map::<L, N>(world, map_id).ok_or(CellError::MapMissing)?;
+    // Check the bounds before taking the cell, so a rejected move leaves it in place
+    let new_c = map_config::<L, N>(world, map_id).resolve(new_c)?;
+    if old_c == new_c {
+        return cell_at::<L, N>(world, map_id, old_c).ok_or(CellError::CellEmpty { cell_c: old_c });
+    }
 
-    for (chunk_id, mut chunk, cells) in cells_with_chunk {
-        for (cell_c, cell_id) in cells {
-            let cell_i = calculate_cell_index(cell_c, L::CHUNK_SIZE);
+    let (_, cell_id) = take_cell_inner::<L, N>(world, Some(map_id), old_c)?;
+    // The moved cell is already out of the map, so the only cell that can be replaced is in the new coordinate
+    let (_, _, result) = insert_cell_inner::<L, N>(
+        world,
+        map_id,
+        new_c,
+        cell_id,
+        Some(InsertPolicy::ReplaceDespawn),
+    );
+    despawn_empty_chunks::<L, N>(world, map_id, [old_c]);
+    send_moved_events::<L, N>(world, map_id, [(old_c, new_c, cell_id)]);
+    send_replaced_events::<L, N>(world, map_id, replaced_cells([(new_c, cell_id, result)]));
+    Ok(cell_id)
+}
 
-            if let Some(cell) = chunk.cells.get_mut(cell_i) {
-                if let Some(old_cell_id) = cell.replace(cell_id) {
-                    world.despawn(old_cell_id);
-                }
-            }
+/// Gets the live cell at a coordinate without taking anything out of the world.
+#[inline]
+fn cell_at<L, const N: usize>(world: &World, map_id: Entity, cell_c: [isize; N]) -> Option<Entity>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let map = world.get::<CellMap<L, N>>(map_id)?;
+    let chunk_size = map.config.chunk_size();
+    let chunk_id = map
+        .chunks
+        .get(&calculate_chunk_coordinate(cell_c, chunk_size).into())?;
+    world
+        .get::<Chunk>(*chunk_id)?
+        .cells
+        .get(calculate_cell_index(cell_c, chunk_size))
+        .copied()
+        .flatten()
+        .filter(|cell_id| world.get_entity(*cell_id).is_some())
+}
 
-            Set::<InChunk<L, N>>::new(cell_id, chunk_id).apply(world);
+/// Inserts a list of entities into the corresponding cells of a given cell map,
+/// following the [InsertPolicy] of the map for occupied coordinates.
+/// Returns what happened to each coordinate, in the order the cells were given.
+pub fn insert_cell_batch<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    cells: impl IntoIterator<Item = ([isize; N], Entity)>,
+) -> Vec<Result<InsertOutcome, CellError<N>>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    insert_cell_batch_on::<L, N>(world, map_id, cells, None)
+}
 
-            world
-                .get_entity_mut(cell_id)
-                .unwrap()
-                .insert((CellIndex::from(cell_i), CellCoord::<N>::new(cell_c)));
-        }
+/// Inserts a list of entities into the corresponding cells of a given cell map,
+/// following the given policy for occupied coordinates.
+/// Returns what happened to each coordinate, in the order the cells were given.
+pub fn insert_cell_batch_with_policy<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    cells: impl IntoIterator<Item = ([isize; N], Entity)>,
+    policy: InsertPolicy,
+) -> Vec<Result<InsertOutcome, CellError<N>>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    insert_cell_batch_on::<L, N>(world, map_id, cells, Some(policy))
+}
+
+/// Inserts a list of entities into a cell map, spawning the map if needed,
+/// then sends the events and returns what happened to each coordinate.
+#[inline]
+fn insert_cell_batch_on<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    cells: impl IntoIterator<Item = ([isize; N], Entity)>,
+    policy: Option<InsertPolicy>,
+) -> Vec<Result<InsertOutcome, CellError<N>>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let map_id = match spawn_or_find_map::<L, N>(world, map_id) {
+        Ok(map_id) => map_id,
+        Err(error) => return cells.into_iter().map(|_| Err(error)).collect(),
+    };
+    let results = insert_cell_batch_inner::<L, N>(world, map_id, cells, policy);
+    send_insert_events::<L, N>(
+        world,
+        map_id,
+        results
+            .iter()
+            .map(|(cell_c, cell_id, result)| (*cell_c, *cell_id, result)),
+    );
+    results.into_iter().map(|(_, _, result)| result).collect()
+}
+
+/// Inserts a list of entities into an existing map without sending events,
+/// returning what happened to each coordinate.
+/// If no policy is given, the policy of the map is used.
+#[inline]
+fn insert_cell_batch_inner<L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    cells: impl IntoIterator<Item = ([isize; N], Entity)>,
+    policy: Option<InsertPolicy>,
+) -> Vec<InsertResult<N>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let config = map_config::<L, N>(world, map_id);
+    let policy = policy.unwrap_or(config.insert_policy());
 
-        world.get_entity_mut(chunk_id).unwrap().insert(chunk);
+    // Cells rejected by the bounds are reported without touching any chunks
+    let mut results = Vec::new();
+    let chunked_cells = cells
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, (cell_c, cell_id))| match config.resolve(cell_c) {
+            Ok(cell_c) => Some((i, cell_c, cell_id)),
+            Err(error) => {
+                results.push((i, (cell_c, cell_id, Err(error))));
+                None
+            }
+        })
+        .group_by(|(_, cell_c, _)| calculate_chunk_coordinate(*cell_c, config.chunk_size()));
+
+    for (chunk_c, cells) in chunked_cells.into_iter() {
+        let chunk_id = spawn_or_find_chunk::<L, N>(world, map_id, chunk_c);
+        for (i, cell_c, cell_id) in cells {
+            let cell_i = calculate_cell_index(cell_c, config.chunk_size());
+            let result = place_cell::<L, N>(world, chunk_id, cell_c, cell_i, cell_id, policy);
+            results.push((i, (cell_c, cell_id, result)));
+        }
     }
 
-    world.get_entity_mut(map_id).unwrap().insert(map);
+    // Chunks are filled in any order, so put the results back in the order the cells were given
+    results.sort_unstable_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
 }
 
+/// The coordinate and entity of an inserted cell, along with what happened to the coordinate.
+type InsertResult<const N: usize> = ([isize; N], Entity, Result<InsertOutcome, CellError<N>>);
+
 /// Removes the cells from the cell map, returning the cell coordinates removed and their corresponding entities.
 pub fn take_cell_batch<L, const N: usize>(
     world: &mut World,
+    map_id: Option<Entity>,
     cells: impl IntoIterator<Item = [isize; N]>,
 ) -> Vec<([isize; N], Entity)>
 where
     L: CellMapLabel + Send + 'static,
 {
-    // Group cells by chunk
-    let chunked_cells = cells
-        .into_iter()
-        .group_by(|cell_c| calculate_chunk_coordinate(*cell_c, L::CHUNK_SIZE));
-
-    // Remove the map, or return if it doesn't exist
-    let (map_id, mut map) = if let Some(map_info) = remove_map::<L, N>(world) {
-        map_info
+    // Get the map, or return if it doesn't exist
+    let map_id = if let Some(map_id) = find_map::<L, N>(world, map_id) {
+        map_id
     } else {
         return Vec::new();
     };
 
-    // Get the chunks and entities from the map
-    let cells_with_chunk = chunked_cells
+    let cell_ids = take_cell_batch_inner::<L, N>(world, map_id, cells);
+    despawn_empty_chunks::<L, N>(world, map_id, cell_ids.iter().map(|(cell_c, _)| *cell_c));
+    cell_ids
+}
+
+/// Removes the cells from the cell map without despawning chunks that are left empty.
+#[inline]
+fn take_cell_batch_inner<L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    cells: impl IntoIterator<Item = [isize; N]>,
+) -> Vec<([isize; N], Entity)>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    // Group cells by chunk
+    let chunk_size = map_config::<L, N>(world, map_id).chunk_size();
+    let chunked_cells = cells
         .into_iter()
-        .filter_map(|(chunk_c, cells)| {
-            remove_chunk(world, &mut map, chunk_c)
-                .map(|chunk_info| (chunk_info.0, chunk_info.1, cells))
-        })
-        .map(|(chunk_id, chunk, cells)| {
-            (
-                chunk_id,
-                chunk,
-                cells.into_iter().collect::<Vec<[isize; N]>>(),
-            )
-        })
-        .collect::<Vec<(Entity, Chunk, Vec<[isize; N]>)>>();
+        .group_by(|cell_c| calculate_chunk_coordinate(*cell_c, chunk_size));
 
     let mut cell_ids = Vec::new();
-    for (chunk_id, mut chunk, cells) in cells_with_chunk {
+    for (chunk_c, cells) in chunked_cells.into_iter() {
+        let Some(chunk_id) = find_chunk::<L, N>(world, map_id, chunk_c) else {
+            continue;
+        };
         for cell_c in cells {
-            let cell_i = calculate_cell_index(cell_c, L::CHUNK_SIZE);
-
-            if let Some(mut cell_e) = chunk
-                .cells
-                .get_mut(cell_i)
-                .and_then(|cell| cell.take())
-                .and_then(|cell_id| world.get_entity_mut(cell_id))
-            {
-                cell_e.remove::<(CellIndex, CellCoord)>();
-                let cell_id = cell_e.id();
-                Unset::<InChunk<L, N>>::new(cell_id, chunk_id).apply(world);
+            let cell_i = calculate_cell_index(cell_c, chunk_size);
+            if let Some(cell_id) = take_from_chunk::<L, N>(world, chunk_id, cell_i) {
                 cell_ids.push((cell_c, cell_id));
             }
         }
-
-        world.get_entity_mut(chunk_id).unwrap().insert(chunk);
     }
 
-    world.get_entity_mut(map_id).unwrap().insert(map);
     cell_ids
 }
 
 /// Insert the given entity into the map and have it treated as a chunk
-pub fn insert_chunk<L, const N: usize>(world: &mut World, chunk_c: [isize; N], chunk_id: Entity)
+pub fn insert_chunk<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    chunk_c: [isize; N],
+    chunk_id: Entity,
+) -> Result<(), CellError<N>>
 where
     L: CellMapLabel + Send + 'static,
 {
-    let (map_id, mut map) = spawn_or_remove_map::<L, N>(world);
+    let map_id = spawn_or_find_map::<L, N>(world, map_id)?;
+    place_chunk::<L, N>(world, map_id, chunk_c, chunk_id);
+    Ok(())
+}
 
+/// Puts an entity into the map as the chunk at a chunk coordinate, despawning any old chunk.
+#[inline]
+fn place_chunk<L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    chunk_c: [isize; N],
+    chunk_id: Entity,
+) where
+    L: CellMapLabel + Send + 'static,
+{
     // Despawn the chunk if it exists
-    if let Some(chunk_id) = map.chunks.insert(chunk_c.into(), chunk_id) {
-        CheckedDespawn(chunk_id).apply(world);
+    if let Some(old_chunk_id) = world
+        .get_mut::<CellMap<L, N>>(map_id)
+        .unwrap()
+        .chunks
+        .insert(chunk_c.into(), chunk_id)
+    {
+        CheckedDespawn(old_chunk_id).apply(world);
     }
 
+    let chunk_size = map_config::<L, N>(world, map_id).chunk_size();
     world.get_entity_mut(chunk_id).unwrap().insert((
-        Chunk::new(L::CHUNK_SIZE.pow(N as u32)),
+        Chunk::new(chunk_size.iter().product()),
         ChunkCoord::from(chunk_c),
     ));
     Set::<InMap<L, N>>::new(chunk_id, map_id).apply(world);
-    map.chunks.insert(chunk_c.into(), chunk_id);
-
-    world.entity_mut(map_id).insert(map);
 }
 
 /// Remove the chunk from the map without despawning it.
 /// # Note
 /// This does not despawn or remove the cell entities, and reinsertion of this entity will not recreate the link to the chunk's cells.
 /// If you wish to take the chunk and delete it's underlying cells, use (take_chunk_despawn_cells)[`take_chunk_despawn_cells`]
-pub fn take_chunk<L, const N: usize>(world: &mut World, chunk_c: [isize; N]) -> Option<Entity>
+pub fn take_chunk<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    chunk_c: [isize; N],
+) -> Option<Entity>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    try_take_chunk::<L, N>(world, map_id, chunk_c).ok()
+}
+
+/// Remove the chunk from the map without despawning it, returning why it couldn't be taken if it fails.
+/// # Note
+/// See [take_chunk] for what happens to the cells in the chunk.
+pub fn try_take_chunk<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    chunk_c: [isize; N],
+) -> Result<Entity, CellError<N>>
 where
     L: CellMapLabel + Send + 'static,
 {
     // Get the map or return
-    let (map_id, mut map) = remove_map::<L, N>(world)?;
+    let map_id = find_map::<L, N>(world, map_id).ok_or(CellError::MapMissing)?;
 
     // Get the old chunk or return
-    let chunk_id = if let Some(mut chunk_e) = map
-        .chunks
-        .remove(&chunk_c.into())
+    let chunk_id = if let Some(mut chunk_e) = remove_chunk_entry::<L, N>(world, map_id, chunk_c)
         .and_then(|chunk_id| world.get_entity_mut(chunk_id))
     {
         chunk_e.remove::<(Chunk, ChunkCoord)>();
         let chunk_id = chunk_e.id();
         Unset::<InMap<L, N>>::new(chunk_id, map_id).apply(world);
         Withdraw::<InChunk<L, N>>::new(chunk_id).apply(world);
-        Some(chunk_id)
+        Ok(chunk_id)
     } else {
-        None
+        Err(CellError::ChunkMissing { chunk_c })
     };
 
-    world.entity_mut(map_id).insert(map);
-
     chunk_id
 }
 
 /// Remove the chunk from the map without despawning it and despawns the cells in the chunk.
 pub fn take_chunk_despawn_cells<L, const N: usize>(
     world: &mut World,
+    map_id: Option<Entity>,
     chunk_c: [isize; N],
 ) -> Option<Entity>
 where
     L: CellMapLabel + Send + 'static,
 {
     // Get the map or return
-    let (map_id, mut map) = remove_map::<L, N>(world)?;
+    let map_id = find_map::<L, N>(world, map_id)?;
 
     // Get the old chunk or return
-    let chunk_id = if let Some(mut chunk_e) = map
-        .chunks
-        .remove(&chunk_c.into())
+    let chunk_id = if let Some(mut chunk_e) = remove_chunk_entry::<L, N>(world, map_id, chunk_c)
         .and_then(|chunk_id| world.get_entity_mut(chunk_id))
     {
         let (chunk, _) = chunk_e.take::<(Chunk, ChunkCoord)>().unwrap();
         let chunk_id = chunk_e.id();
-        for cell_id in chunk.cells.into_iter().flatten() {
-            world.despawn(cell_id);
-        }
+        despawn_chunk_cells::<L, N>(world, map_id, chunk);
         Unset::<InMap<L, N>>::new(chunk_id, map_id).apply(world);
         Withdraw::<InChunk<L, N>>::new(chunk_id).apply(world);
         Some(chunk_id)
@@ -563,37 +1080,25 @@ where
         None
     };
 
-    world.entity_mut(map_id).insert(map);
-
     chunk_id
 }
 
 /// Inserts a list of entities into map and treats them as chunks
 pub fn insert_chunk_batch<L, const N: usize>(
     world: &mut World,
+    map_id: Option<Entity>,
     chunks: impl IntoIterator<Item = ([isize; N], Entity)>,
-) where
+) -> Result<(), CellError<N>>
+where
     L: CellMapLabel + Send + 'static,
 {
-    // Remove the map, or spawn an entity to hold the map, then create an empty map
-    let (map_id, mut map) = spawn_or_remove_map::<L, N>(world);
+    // Get the map, or spawn an entity to hold an empty map
+    let map_id = spawn_or_find_map::<L, N>(world, map_id)?;
 
-    // Get the chunks and entities from the map
     for (chunk_c, chunk_id) in chunks.into_iter() {
-        // Despawn the chunk if it exists
-        if let Some(chunk_id) = map.chunks.insert(chunk_c.into(), chunk_id) {
-            CheckedDespawn(chunk_id).apply(world);
-        }
-
-        world.get_entity_mut(chunk_id).unwrap().insert((
-            Chunk::new(L::CHUNK_SIZE.pow(N as u32)),
-            ChunkCoord::from(chunk_c),
-        ));
-        Set::<InMap<L, N>>::new(chunk_id, map_id).apply(world);
-        map.chunks.insert(chunk_c.into(), chunk_id);
+        place_chunk::<L, N>(world, map_id, chunk_c, chunk_id);
     }
-
-    world.get_entity_mut(map_id).unwrap().insert(map);
+    Ok(())
 }
 
 /// Removes the chunks from the cell map, returning the chunk coordinates removed and their corresponding entities.
@@ -602,14 +1107,15 @@ pub fn insert_chunk_batch<L, const N: usize>(
 /// If you wish to take the chunk and delete it's underlying cells, use (take_chunk_batch_despawn_cells)[`take_chunk_batch_despawn_cells`]
 pub fn take_chunk_batch<L, const N: usize>(
     world: &mut World,
+    map_id: Option<Entity>,
     chunks: impl IntoIterator<Item = [isize; N]>,
 ) -> Vec<([isize; N], Entity)>
 where
     L: CellMapLabel + Send + 'static,
 {
-    // Remove the map, or return if it doesn't exist
-    let (map_id, mut map) = if let Some(map_info) = remove_map::<L, N>(world) {
-        map_info
+    // Get the map, or return if it doesn't exist
+    let map_id = if let Some(map_id) = find_map::<L, N>(world, map_id) {
+        map_id
     } else {
         return Vec::new();
     };
@@ -618,9 +1124,7 @@ where
 
     for chunk_c in chunks.into_iter() {
         // Get the old chunk or return
-        if let Some(mut chunk_e) = map
-            .chunks
-            .remove(&chunk_c.into())
+        if let Some(mut chunk_e) = remove_chunk_entry::<L, N>(world, map_id, chunk_c)
             .and_then(|chunk_id| world.get_entity_mut(chunk_id))
         {
             chunk_e.remove::<(Chunk, ChunkCoord)>();
@@ -631,7 +1135,6 @@ where
         };
     }
 
-    world.get_entity_mut(map_id).unwrap().insert(map);
     chunk_ids
 }
 
@@ -639,14 +1142,15 @@ where
 /// Also despawns all cells in all the removed chunks.
 pub fn take_chunk_batch_despawn_cells<L, const N: usize>(
     world: &mut World,
+    map_id: Option<Entity>,
     chunks: impl IntoIterator<Item = [isize; N]>,
 ) -> Vec<([isize; N], Entity)>
 where
     L: CellMapLabel + Send + 'static,
 {
-    // Remove the map, or return if it doesn't exist
-    let (map_id, mut map) = if let Some(map_info) = remove_map::<L, N>(world) {
-        map_info
+    // Get the map, or return if it doesn't exist
+    let map_id = if let Some(map_id) = find_map::<L, N>(world, map_id) {
+        map_id
     } else {
         return Vec::new();
     };
@@ -655,26 +1159,205 @@ where
 
     for chunk_c in chunks.into_iter() {
         // Get the old chunk or return
-        if let Some(mut chunk_e) = map
-            .chunks
-            .remove(&chunk_c.into())
+        if let Some(mut chunk_e) = remove_chunk_entry::<L, N>(world, map_id, chunk_c)
             .and_then(|chunk_id| world.get_entity_mut(chunk_id))
         {
             let (chunk, _) = chunk_e.take::<(Chunk, ChunkCoord)>().unwrap();
             let chunk_id = chunk_e.id();
-            for cell_id in chunk.cells.into_iter().flatten() {
-                world.despawn(cell_id);
-            }
+            despawn_chunk_cells::<L, N>(world, map_id, chunk);
             Unset::<InMap<L, N>>::new(chunk_id, map_id).apply(world);
             Withdraw::<InChunk<L, N>>::new(chunk_id).apply(world);
             chunk_ids.push((chunk_c, chunk_id));
         };
     }
 
-    world.get_entity_mut(map_id).unwrap().insert(map);
     chunk_ids
 }
 
+/// Removes a chunk coordinate from the map, returning the chunk entity that was there.
+#[inline]
+fn remove_chunk_entry<L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    chunk_c: [isize; N],
+) -> Option<Entity>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    world
+        .get_mut::<CellMap<L, N>>(map_id)?
+        .chunks
+        .remove(&chunk_c.into())
+}
+
+/// Despawns all the cells in a chunk that has been removed from the world.
+#[inline]
+fn despawn_chunk_cells<L, const N: usize>(world: &mut World, map_id: Entity, chunk: Chunk)
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let mut despawned = Vec::new();
+    for cell in chunk.cells.into_iter().flatten() {
+        if let Some(cell) = world.get_entity_mut(cell) {
+            // Read the coordinate before the cell goes
+            let cell_c = cell.get::<CellCoord<N>>().map(|cell_c| **cell_c);
+            let cell_id = cell.id();
+            cell.despawn();
+            despawned.extend(cell_c.map(|cell_c| (cell_c, cell_id)));
+        }
+    }
+    send_despawned_events::<L, N>(world, map_id, despawned);
+}
+
+/// Gets the map entity for a command, without spawning one.
+/// If no map entity is given, the single map for the label is used.
+#[inline]
+pub(crate) fn find_map<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+) -> Option<Entity>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    if let Some(map_id) = map_id {
+        world.get::<CellMap<L, N>>(map_id).map(|_| map_id)
+    } else {
+        world
+            .query_filtered::<Entity, With<CellMap<L, N>>>()
+            .get_single(world)
+            .ok()
+    }
+}
+
+/// Sends a [CellCommandFailed] event for a failed command, or logs a warning if the event isn't registered.
+#[inline]
+pub(crate) fn report_error<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    error: CellError<N>,
+) where
+    L: CellMapLabel + Send + 'static,
+{
+    if let Some(mut events) = world.get_resource_mut::<Events<CellCommandFailed<L, N>>>() {
+        events.send(CellCommandFailed {
+            map_id,
+            error,
+            label: PhantomData,
+        });
+    } else {
+        warn!("{error}");
+    }
+}
+
+/// Sends a [CellDespawned] event for each of the given cells.
+#[inline]
+fn send_despawned_events<L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    cells: impl IntoIterator<Item = ([isize; N], Entity)>,
+) where
+    L: CellMapLabel + Send + 'static,
+{
+    if let Some(mut events) = world.get_resource_mut::<Events<CellDespawned<L, N>>>() {
+        events.extend(cells.into_iter().map(|(cell_c, cell_id)| CellDespawned {
+            map_id,
+            cell_c,
+            entity: cell_id,
+            label: PhantomData,
+        }));
+    }
+}
+
+/// Sends a [CellReplaced] event for each of the given `(cell_c, old, new)` cells.
+#[inline]
+fn send_replaced_events<L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    cells: impl IntoIterator<Item = ([isize; N], Entity, Entity)>,
+) where
+    L: CellMapLabel + Send + 'static,
+{
+    if let Some(mut events) = world.get_resource_mut::<Events<CellReplaced<L, N>>>() {
+        events.extend(cells.into_iter().map(|(cell_c, old, new)| CellReplaced {
+            map_id,
+            cell_c,
+            old,
+            new,
+            label: PhantomData,
+        }));
+    }
+}
+
+/// Sends a [CellSpawned] event for each inserted cell, and a [CellReplaced] event
+/// for each cell that was despawned or returned to make room.
+#[inline]
+fn send_insert_events<'a, L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    results: impl IntoIterator<Item = ([isize; N], Entity, &'a Result<InsertOutcome, CellError<N>>)>,
+) where
+    L: CellMapLabel + Send + 'static,
+{
+    let mut spawned = Vec::new();
+    let mut replaced = Vec::new();
+    for (cell_c, cell_id, result) in results {
+        match result {
+            Ok(InsertOutcome::Replaced(old_cell_id) | InsertOutcome::Returned(old_cell_id)) => {
+                spawned.push((cell_c, cell_id));
+                replaced.push((cell_c, *old_cell_id, cell_id));
+            }
+            Ok(outcome) if outcome.is_inserted() => spawned.push((cell_c, cell_id)),
+            _ => {}
+        }
+    }
+
+    if let Some(mut events) = world.get_resource_mut::<Events<CellSpawned<L, N>>>() {
+        events.extend(spawned.into_iter().map(|(cell_c, cell_id)| CellSpawned {
+            map_id,
+            cell_c,
+            entity: cell_id,
+            label: PhantomData,
+        }));
+    }
+    send_replaced_events::<L, N>(world, map_id, replaced);
+}
+
+/// Gets the `(cell_c, old, new)` cells that were despawned or returned to make room for inserted cells.
+#[inline]
+fn replaced_cells<const N: usize>(
+    results: impl IntoIterator<Item = InsertResult<N>>,
+) -> Vec<([isize; N], Entity, Entity)> {
+    results
+        .into_iter()
+        .filter_map(|(cell_c, cell_id, result)| match result {
+            Ok(InsertOutcome::Replaced(old_cell_id) | InsertOutcome::Returned(old_cell_id)) => {
+                Some((cell_c, old_cell_id, cell_id))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sends a [CellMoved] event for each of the given `(from, to, entity)` cells.
+#[inline]
+fn send_moved_events<L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    cells: impl IntoIterator<Item = ([isize; N], [isize; N], Entity)>,
+) where
+    L: CellMapLabel + Send + 'static,
+{
+    if let Some(mut events) = world.get_resource_mut::<Events<CellMoved<L, N>>>() {
+        events.extend(cells.into_iter().map(|(from, to, entity)| CellMoved {
+            map_id,
+            from,
+            to,
+            entity,
+            label: PhantomData,
+        }));
+    }
+}
+
 trait GroupBy: Iterator {
     fn group_by<F, K>(
         self,
@@ -716,3 +1399,324 @@ where
         map.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::{change_detection::DetectChanges, system::CommandQueue};
+    use rstest::rstest;
+
+    use super::*;
+    use crate::cells::{data::CellData, BoundsPolicy};
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 16;
+    }
+
+    struct SparseLayer;
+
+    impl CellMapLabel for SparseLayer {
+        const CHUNK_SIZE: usize = 4;
+        const DESPAWN_EMPTY_CHUNKS: bool = true;
+    }
+
+    struct WideLayer;
+
+    impl CellMapLabel for WideLayer {
+        const CHUNK_SIZE: usize = 8;
+        const CHUNK_EXTENT: &'static [usize] = &[8, 2];
+    }
+
+    #[rstest]
+    #[case(InsertPolicy::ReplaceDespawn, true, false, true)]
+    #[case(InsertPolicy::ReplaceReturn, true, true, true)]
+    #[case(InsertPolicy::Reject, false, true, false)]
+    #[case(InsertPolicy::Error, false, true, true)]
+    fn insert_policies(
+        #[case] policy: InsertPolicy,
+        #[case] replaced: bool,
+        #[case] old_alive: bool,
+        #[case] new_alive: bool,
+    ) {
+        let mut world = World::new();
+        let old = world.spawn_empty().id();
+        let new = world.spawn_empty().id();
+        insert_cell::<TestLayer, 2>(&mut world, None, [3, -4], old).unwrap();
+
+        let result =
+            insert_cell_with_policy::<TestLayer, 2>(&mut world, None, [3, -4], new, policy);
+        match policy {
+            InsertPolicy::ReplaceDespawn => assert_eq!(result, Ok(InsertOutcome::Replaced(old))),
+            InsertPolicy::ReplaceReturn => assert_eq!(result, Ok(InsertOutcome::Returned(old))),
+            InsertPolicy::Reject => assert_eq!(result, Ok(InsertOutcome::Rejected(new))),
+            InsertPolicy::Error => assert_eq!(
+                result,
+                Err(CellError::Occupied {
+                    cell_c: [3, -4],
+                    occupant: old
+                })
+            ),
+        }
+
+        let occupant = world.query::<&Chunk>().single(&world).cells
+            [calculate_cell_index([3, -4], TestLayer::chunk_size())];
+        assert_eq!(occupant, Some(if replaced { new } else { old }));
+        assert_eq!(world.get_entity(old).is_some(), old_alive);
+        assert_eq!(world.get_entity(new).is_some(), new_alive);
+        if policy == InsertPolicy::ReplaceReturn {
+            assert!(world.get::<CellCoord>(old).is_none());
+        }
+    }
+
+    #[test]
+    fn edits_keep_map_and_chunk_in_place() {
+        let mut world = World::new();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        insert_cell_batch::<TestLayer, 2>(&mut world, None, [([0, 0], first), ([1, 0], second)]);
+
+        let map_id = find_map::<TestLayer, 2>(&mut world, None).unwrap();
+        let chunk_id = find_chunk::<TestLayer, 2>(&world, map_id, [0, 0]).unwrap();
+        world.clear_trackers();
+
+        // Moving a cell within a chunk shouldn't take the map or chunk out of the world
+        move_cell::<TestLayer, 2>(&mut world, None, [1, 0], [2, 0]).unwrap();
+        let map = world
+            .entity(map_id)
+            .get_ref::<CellMap<TestLayer, 2>>()
+            .unwrap();
+        assert!(!map.is_changed());
+        let chunk = world.entity(chunk_id).get_ref::<Chunk>().unwrap();
+        assert!(!chunk.is_added());
+        assert_eq!(
+            cell_at::<TestLayer, 2>(&world, map_id, [2, 0]),
+            Some(second)
+        );
+    }
+
+    #[test]
+    fn despawns_empty_chunks() {
+        let mut world = World::new();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+        insert_cell_batch::<SparseLayer, 2>(&mut world, None, [([0, 0], first), ([1, 0], second)]);
+        let map_id = find_map::<SparseLayer, 2>(&mut world, None).unwrap();
+        let chunk_id = find_chunk::<SparseLayer, 2>(&world, map_id, [0, 0]).unwrap();
+
+        // Moving the last cell out of a chunk only despawns it once it's gone
+        take_cell::<SparseLayer, 2>(&mut world, None, [0, 0]).unwrap();
+        move_cell::<SparseLayer, 2>(&mut world, None, [1, 0], [2, 0]).unwrap();
+        assert_eq!(
+            find_chunk::<SparseLayer, 2>(&world, map_id, [0, 0]),
+            Some(chunk_id)
+        );
+
+        move_cell::<SparseLayer, 2>(&mut world, None, [2, 0], [9, 0]).unwrap();
+        assert!(world.get_entity(chunk_id).is_none());
+        let map = world.get::<CellMap<SparseLayer, 2>>(map_id).unwrap();
+        assert_eq!(map.chunks.len(), 1);
+
+        take_cell_batch::<SparseLayer, 2>(&mut world, None, [[9, 0]]);
+        let map = world.get::<CellMap<SparseLayer, 2>>(map_id).unwrap();
+        assert!(map.chunks.is_empty());
+        assert_eq!(world.query::<&Chunk>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn keeps_chunks_with_data() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let mut cells = commands.cells::<SparseLayer, 2>();
+        cells.set_cell_data([0, 0], 3u8);
+        cells.spawn_cell([1, 0], ());
+        cells.spawn_cell([4, 0], ());
+        queue.apply(&mut world);
+
+        take_cell_batch::<SparseLayer, 2>(&mut world, None, [[1, 0], [4, 0]]);
+        let map_id = find_map::<SparseLayer, 2>(&mut world, None).unwrap();
+        let chunk_id = find_chunk::<SparseLayer, 2>(&world, map_id, [0, 0]).unwrap();
+        assert_eq!(
+            world.get::<CellData<u8>>(chunk_id).unwrap().get(0),
+            Some(&3)
+        );
+        assert_eq!(find_chunk::<SparseLayer, 2>(&world, map_id, [1, 0]), None);
+    }
+
+    #[test]
+    fn non_cubic_chunks() {
+        let mut world = World::new();
+        let cell = world.spawn_empty().id();
+        insert_cell::<WideLayer, 2>(&mut world, None, [7, 3], cell).unwrap();
+
+        let map_id = find_map::<WideLayer, 2>(&mut world, None).unwrap();
+        let chunk_id = find_chunk::<WideLayer, 2>(&world, map_id, [0, 1]).unwrap();
+        assert_eq!(world.get::<Chunk>(chunk_id).unwrap().cells.len(), 16);
+        assert_eq!(cell_at::<WideLayer, 2>(&world, map_id, [7, 3]), Some(cell));
+    }
+
+    #[test]
+    fn batch_results_in_input_order() {
+        let mut world = World::new();
+        let config = CellMapConfig::<TestLayer>::default().with_bounds([0, 0], [63, 63]);
+        let map_id = world.spawn(CellMap::new(config)).id();
+        let cell_cs = [[0, 0], [40, 0], [-5, -5], [20, 20], [0, 0], [60, 60]];
+        let cells = cell_cs
+            .into_iter()
+            .map(|cell_c| (cell_c, world.spawn_empty().id()))
+            .collect::<Vec<_>>();
+
+        let results = insert_cell_batch_with_policy::<TestLayer, 2>(
+            &mut world,
+            Some(map_id),
+            cells.clone(),
+            InsertPolicy::Error,
+        );
+        assert_eq!(
+            results,
+            vec![
+                Ok(InsertOutcome::Inserted),
+                Ok(InsertOutcome::Inserted),
+                Err(CellError::OutOfBounds { cell_c: [-5, -5] }),
+                Ok(InsertOutcome::Inserted),
+                Err(CellError::Occupied {
+                    cell_c: [0, 0],
+                    occupant: cells[0].1
+                }),
+                Ok(InsertOutcome::Inserted),
+            ]
+        );
+    }
+
+    #[test]
+    fn commands_on_despawned_maps() {
+        let mut world = World::new();
+        world.init_resource::<Events<CellCommandFailed<TestLayer>>>();
+        let map_id = world.spawn(CellMap::<TestLayer>::default()).id();
+        world.despawn(map_id);
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let mut cells = commands.cells_on::<TestLayer, 2>(map_id);
+        cells.spawn_cell([0, 0], ());
+        cells.spawn_cell_batch([[1, 0], [2, 0]], |_| ());
+        cells.set_cell_data([0, 0], 1u8);
+        cells.spawn_chunk([0, 0], ());
+        queue.apply(&mut world);
+
+        assert_eq!(world.entities().len(), 0);
+        let errors = world
+            .resource_mut::<Events<CellCommandFailed<TestLayer>>>()
+            .drain()
+            .map(|event| (event.map_id, event.error))
+            .collect::<Vec<_>>();
+        assert_eq!(errors, vec![(Some(map_id), CellError::MapMissing); 4]);
+    }
+
+    #[test]
+    fn despawned_chunk_cells_keep_their_coords() {
+        let mut world = World::new();
+        world.init_resource::<Events<CellDespawned<TestLayer, 2>>>();
+        for cell_c in [[-16, 0], [-31, 15]] {
+            let cell = world.spawn_empty().id();
+            insert_cell::<TestLayer, 2>(&mut world, None, cell_c, cell).unwrap();
+        }
+
+        assert!(take_chunk_despawn_cells::<TestLayer, 2>(&mut world, None, [-2, 0]).is_some());
+        let mut cell_cs = world
+            .resource_mut::<Events<CellDespawned<TestLayer, 2>>>()
+            .drain()
+            .map(|event| event.cell_c)
+            .collect::<Vec<_>>();
+        cell_cs.sort();
+        assert_eq!(cell_cs, vec![[-31, 15], [-16, 0]]);
+    }
+
+    #[rstest]
+    #[case(BoundsPolicy::Reject)]
+    #[case(BoundsPolicy::Clamp)]
+    fn bounded_maps(#[case] policy: BoundsPolicy) {
+        let mut world = World::new();
+        let config = CellMapConfig::<TestLayer>::default()
+            .with_bounds([9, 9], [0, 0])
+            .with_bounds_policy(policy);
+        let map_id = world.spawn(CellMap::new(config)).id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let mut cells = commands.cells_on::<TestLayer, 2>(map_id);
+        let outside = cells.spawn_cell([12, -3], ()).id();
+        cells.spawn_cell_batch([[4, 4], [4, 10]], |_| ());
+        queue.apply(&mut world);
+
+        let inside = cell_at::<TestLayer, 2>(&world, map_id, [4, 4]).unwrap();
+        let moved = move_cell::<TestLayer, 2>(&mut world, Some(map_id), [4, 4], [-1, 4]);
+        match policy {
+            BoundsPolicy::Reject => {
+                assert!(world.get_entity(outside).is_none());
+                assert_eq!(world.query::<&CellCoord>().iter(&world).count(), 1);
+                assert_eq!(moved, Err(CellError::OutOfBounds { cell_c: [-1, 4] }));
+                assert_eq!(
+                    cell_at::<TestLayer, 2>(&world, map_id, [4, 4]),
+                    Some(inside)
+                );
+            }
+            BoundsPolicy::Clamp => {
+                assert_eq!(
+                    cell_at::<TestLayer, 2>(&world, map_id, [9, 0]),
+                    Some(outside)
+                );
+                assert!(cell_at::<TestLayer, 2>(&world, map_id, [4, 9]).is_some());
+                assert_eq!(moved, Ok(inside));
+                assert_eq!(
+                    cell_at::<TestLayer, 2>(&world, map_id, [0, 4]),
+                    Some(inside)
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(BoundsPolicy::Reject, vec![[9, 3]], vec![[12, 3], [15, 3], [12, 4]])]
+    #[case(BoundsPolicy::Clamp, vec![[9, 3], [9, 4]], vec![[12, 3], [15, 3]])]
+    fn bounded_batches_report_dropped_cells(
+        #[case] policy: BoundsPolicy,
+        #[case] spawned: Vec<[isize; 2]>,
+        #[case] dropped: Vec<[isize; 2]>,
+    ) {
+        let mut world = World::new();
+        world.init_resource::<Events<CellCommandFailed<TestLayer>>>();
+        let config = CellMapConfig::<TestLayer>::default()
+            .with_bounds([0, 0], [9, 9])
+            .with_bounds_policy(policy);
+        let map_id = world.spawn(CellMap::new(config)).id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands
+            .cells_on::<TestLayer, 2>(map_id)
+            .spawn_cell_batch([[12, 3], [9, 3], [15, 3], [12, 4]], |_| ());
+        queue.apply(&mut world);
+
+        let mut cells = world
+            .query::<&CellCoord>()
+            .iter(&world)
+            .map(|cell_c| **cell_c)
+            .collect::<Vec<_>>();
+        cells.sort();
+        assert_eq!(cells, spawned);
+        let errors = world
+            .resource_mut::<Events<CellCommandFailed<TestLayer>>>()
+            .drain()
+            .map(|event| event.error)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            dropped
+                .into_iter()
+                .map(|cell_c| CellError::OutOfBounds { cell_c })
+                .collect::<Vec<_>>()
+        );
+    }
+}
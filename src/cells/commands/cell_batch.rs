@@ -1,12 +1,16 @@
 use bevy::{
     ecs::{bundle::Bundle, entity::Entity, system::Command, world::World},
-    utils::HashMap,
+    utils::{HashMap, HashSet},
 };
 use bimap::BiMap;
 
-use crate::prelude::{commands::insert_cell_batch, CellMapLabel};
+use crate::prelude::{commands::insert_cell_batch, CellError, CellMapLabel, InsertPolicy};
 
-use super::take_cell_batch;
+use super::{
+    despawn_empty_chunks, find_map, insert_cell_batch_inner, map_config, replaced_cells,
+    report_error, send_despawned_events, send_moved_events, send_replaced_events,
+    spawn_or_find_map, take_cell_batch, take_cell_batch_inner,
+};
 
 pub struct SpawnCellBatch<L, F, B, IC, const N: usize = 2>
 where
@@ -15,6 +19,7 @@ where
     B: Bundle + Send + 'static,
     IC: IntoIterator<Item = [isize; N]> + Send + 'static,
 {
+    pub map_id: Option<Entity>,
     pub cell_cs: IC,
     pub bundle_f: F,
     pub label: std::marker::PhantomData<L>,
@@ -28,18 +33,57 @@ where
     IC: IntoIterator<Item = [isize; N]> + Send + 'static,
 {
     fn apply(self, world: &mut World) {
-        let (cell_cs, bundles): (Vec<[isize; N]>, Vec<B>) = self
+        let map_id = match spawn_or_find_map::<L, N>(world, self.map_id) {
+            Ok(map_id) => map_id,
+            Err(error) => {
+                report_error::<L, N>(world, self.map_id, error);
+                return;
+            }
+        };
+
+        // Only spawn the cells that end up inside the bounds of the map
+        let config = map_config::<L, N>(world, map_id);
+        let mut errors = Vec::new();
+        let resolved = self
             .cell_cs
             .into_iter()
-            .map(|coord| (coord, (self.bundle_f)(coord)))
+            .filter_map(|cell_c| match config.resolve(cell_c) {
+                Ok(new_c) => Some((cell_c, new_c)),
+                Err(error) => {
+                    errors.push(error);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Clamped cells don't replace cells of the same batch, they're dropped like rejected ones
+        let mut claimed = resolved
+            .iter()
+            .filter(|(cell_c, new_c)| cell_c == new_c)
+            .map(|(_, new_c)| *new_c)
+            .collect::<HashSet<_>>();
+        let (cell_cs, bundles): (Vec<[isize; N]>, Vec<B>) = resolved
+            .into_iter()
+            .filter(|(cell_c, new_c)| {
+                if cell_c == new_c || claimed.insert(*new_c) {
+                    true
+                } else {
+                    errors.push(CellError::OutOfBounds { cell_c: *cell_c });
+                    false
+                }
+            })
+            .map(|(_, new_c)| (new_c, (self.bundle_f)(new_c)))
             .unzip();
+        for error in errors {
+            report_error::<L, N>(world, self.map_id, error);
+        }
 
         let cells = cell_cs
             .into_iter()
             .zip(world.spawn_batch(bundles))
             .collect::<Vec<([isize; N], Entity)>>();
 
-        insert_cell_batch::<L, N>(world, cells);
+        insert_cell_batch::<L, N>(world, Some(map_id), cells);
     }
 }
 
@@ -48,6 +92,7 @@ where
     L: CellMapLabel + Send + 'static,
     IC: IntoIterator<Item = [isize; N]> + Send + 'static,
 {
+    pub map_id: Option<Entity>,
     pub cell_cs: IC,
     pub label: std::marker::PhantomData<L>,
 }
@@ -58,9 +103,17 @@ where
     IC: IntoIterator<Item = [isize; N]> + Send + 'static,
 {
     fn apply(self, world: &mut World) {
-        for (_, cell_id) in take_cell_batch::<L, N>(world, self.cell_cs) {
-            world.despawn(cell_id);
+        let map_id = if let Some(map_id) = find_map::<L, N>(world, self.map_id) {
+            map_id
+        } else {
+            return;
+        };
+
+        let removed = take_cell_batch::<L, N>(world, Some(map_id), self.cell_cs);
+        for (_, cell_id) in removed.iter() {
+            world.despawn(*cell_id);
         }
+        send_despawned_events::<L, N>(world, map_id, removed);
     }
 }
 
@@ -69,6 +122,7 @@ where
     L: CellMapLabel + Send + 'static,
     IC: IntoIterator<Item = ([isize; N], [isize; N])> + Send + 'static,
 {
+    pub map_id: Option<Entity>,
     pub cell_cs: IC,
     pub label: std::marker::PhantomData<L>,
 }
@@ -82,17 +136,40 @@ where
         const ERR_MESSAGE: &str =
             "Couldn't find cell coord in batch move.  Maybe repeated cell coord in command.";
 
+        let map_id = if let Some(map_id) = find_map::<L, N>(world, self.map_id) {
+            map_id
+        } else {
+            return;
+        };
+
+        // Moves rejected by the bounds leave their cells in place
+        let config = map_config::<L, N>(world, map_id);
         let mut cell_cs = self
             .cell_cs
             .into_iter()
+            .filter_map(|(old_c, new_c)| Some((old_c, config.resolve(new_c).ok()?)))
             .collect::<HashMap<[isize; N], [isize; N]>>();
 
-        let removed =
-            take_cell_batch::<L, N>(world, cell_cs.keys().cloned().collect::<Vec<[isize; N]>>())
-                .into_iter()
-                .map(|(cell_c, cell_id)| (cell_cs.remove(&cell_c).expect(ERR_MESSAGE), cell_id));
+        let moved = take_cell_batch_inner::<L, N>(
+            world,
+            map_id,
+            cell_cs.keys().cloned().collect::<Vec<[isize; N]>>(),
+        )
+        .into_iter()
+        .map(|(cell_c, cell_id)| (cell_c, cell_cs.remove(&cell_c).expect(ERR_MESSAGE), cell_id))
+        .collect::<Vec<([isize; N], [isize; N], Entity)>>();
 
-        insert_cell_batch::<L, N>(world, removed);
+        let results = insert_cell_batch_inner::<L, N>(
+            world,
+            map_id,
+            moved.iter().map(|(_, new_c, cell_id)| (*new_c, *cell_id)),
+            Some(InsertPolicy::ReplaceDespawn),
+        );
+
+        // Only sweep the chunks once the cells are back in, so moves within a chunk keep it
+        despawn_empty_chunks::<L, N>(world, map_id, moved.iter().map(|(old_c, _, _)| *old_c));
+        send_moved_events::<L, N>(world, map_id, moved);
+        send_replaced_events::<L, N>(world, map_id, replaced_cells(results));
     }
 }
 
@@ -101,6 +178,7 @@ where
     L: CellMapLabel + Send + 'static,
     IC: IntoIterator<Item = ([isize; N], [isize; N])> + Send + 'static,
 {
+    pub map_id: Option<Entity>,
     pub cell_cs: IC,
     pub label: std::marker::PhantomData<L>,
 }
@@ -114,25 +192,60 @@ where
         const ERR_MESSAGE: &str =
             "Couldn't find cell coord in batch move.  Maybe repeated cell coord in command.";
 
+        let map_id = if let Some(map_id) = find_map::<L, N>(world, self.map_id) {
+            map_id
+        } else {
+            return;
+        };
+
+        // Swaps can't clamp, so any pair leaving the bounds of the map is skipped
+        let config = map_config::<L, N>(world, map_id);
         let cell_cs = self
             .cell_cs
             .into_iter()
+            .filter(|(cell_c_1, cell_c_2)| config.contains(*cell_c_1) && config.contains(*cell_c_2))
             .collect::<BiMap<[isize; N], [isize; N]>>();
 
-        let removed_left = take_cell_batch::<L, N>(
+        let removed_left = take_cell_batch_inner::<L, N>(
             world,
+            map_id,
             cell_cs.left_values().cloned().collect::<Vec<[isize; N]>>(),
         )
         .into_iter()
-        .map(|(cell_c, cell_id)| (*cell_cs.get_by_left(&cell_c).expect(ERR_MESSAGE), cell_id));
-
-        let removed_right = take_cell_batch::<L, N>(
+        .map(|(cell_c, cell_id)| {
+            (
+                cell_c,
+                *cell_cs.get_by_left(&cell_c).expect(ERR_MESSAGE),
+                cell_id,
+            )
+        });
+
+        let removed_right = take_cell_batch_inner::<L, N>(
             world,
+            map_id,
             cell_cs.right_values().cloned().collect::<Vec<[isize; N]>>(),
         )
         .into_iter()
-        .map(|(cell_c, cell_id)| (*cell_cs.get_by_right(&cell_c).expect(ERR_MESSAGE), cell_id));
+        .map(|(cell_c, cell_id)| {
+            (
+                cell_c,
+                *cell_cs.get_by_right(&cell_c).expect(ERR_MESSAGE),
+                cell_id,
+            )
+        });
+
+        let moved = removed_left
+            .chain(removed_right)
+            .collect::<Vec<([isize; N], [isize; N], Entity)>>();
+
+        insert_cell_batch_inner::<L, N>(
+            world,
+            map_id,
+            moved.iter().map(|(_, new_c, cell_id)| (*new_c, *cell_id)),
+            Some(InsertPolicy::ReplaceDespawn),
+        );
 
-        insert_cell_batch::<L, N>(world, removed_left.chain(removed_right));
+        despawn_empty_chunks::<L, N>(world, map_id, moved.iter().map(|(old_c, _, _)| *old_c));
+        send_moved_events::<L, N>(world, map_id, moved);
     }
 }
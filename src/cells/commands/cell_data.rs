@@ -0,0 +1,51 @@
+use bevy::ecs::{entity::Entity, system::Command, world::World};
+
+use crate::prelude::{
+    calculate_cell_index, calculate_chunk_coordinate, CellData, CellMapLabel, Chunk, HasCellData,
+};
+
+use super::{map_config, report_error, spawn_or_find_chunk, spawn_or_find_map};
+
+pub struct SetCellData<L, T, IC, const N: usize = 2>
+where
+    L: CellMapLabel + Send + 'static,
+    T: Default + Clone + Send + Sync + 'static,
+    IC: IntoIterator<Item = ([isize; N], T)> + Send + 'static,
+{
+    pub map_id: Option<Entity>,
+    pub values: IC,
+    pub label: std::marker::PhantomData<L>,
+}
+
+impl<L, T, IC, const N: usize> Command for SetCellData<L, T, IC, N>
+where
+    L: CellMapLabel + Send + 'static,
+    T: Default + Clone + Send + Sync + 'static,
+    IC: IntoIterator<Item = ([isize; N], T)> + Send + 'static,
+{
+    fn apply(self, world: &mut World) {
+        let map_id = match spawn_or_find_map::<L, N>(world, self.map_id) {
+            Ok(map_id) => map_id,
+            Err(error) => {
+                report_error::<L, N>(world, self.map_id, error);
+                return;
+            }
+        };
+        let chunk_size = map_config::<L, N>(world, map_id).chunk_size();
+
+        for (cell_c, value) in self.values {
+            let chunk_c = calculate_chunk_coordinate(cell_c, chunk_size);
+            let chunk_id = spawn_or_find_chunk::<L, N>(world, map_id, chunk_c);
+
+            let mut chunk_e = world.entity_mut(chunk_id);
+            if !chunk_e.contains::<CellData<T>>() {
+                let len = chunk_e.get::<Chunk>().unwrap().cells.len();
+                chunk_e.insert((CellData::<T>::new(len), HasCellData));
+            }
+            let mut data = chunk_e.get_mut::<CellData<T>>().unwrap();
+            if let Some(old) = data.get_mut(calculate_cell_index(cell_c, chunk_size)) {
+                *old = value;
+            }
+        }
+    }
+}
@@ -1,13 +1,20 @@
 use aery::edges::CheckedDespawn;
 use bevy::ecs::{entity::Entity, system::Command, world::World};
 
-use crate::prelude::CellMapLabel;
+use crate::prelude::{CellError, CellMapLabel, InsertPolicy};
 
-use super::{insert_cell, take_cell};
+use super::{
+    cell_at, despawn_empty_chunks, find_map, insert_cell, insert_cell_inner,
+    insert_cell_with_policy, map_config, move_cell, report_error, send_despawned_events,
+    send_moved_events, take_cell_inner, try_take_cell,
+};
 
 pub struct SpawnCell<L, const N: usize = 2> {
+    pub map_id: Option<Entity>,
     pub cell_c: [isize; N],
     pub cell_id: Entity,
+    /// The policy for an occupied coordinate, `None` uses the policy of the map.
+    pub policy: Option<InsertPolicy>,
     pub label: std::marker::PhantomData<L>,
 }
 
@@ -16,12 +23,30 @@ where
     L: CellMapLabel + Send + 'static,
 {
     fn apply(self, world: &mut World) {
-        insert_cell::<L, N>(world, self.cell_c, self.cell_id)
+        let result = match self.policy {
+            Some(policy) => insert_cell_with_policy::<L, N>(
+                world,
+                self.map_id,
+                self.cell_c,
+                self.cell_id,
+                policy,
+            ),
+            None => insert_cell::<L, N>(world, self.map_id, self.cell_c, self.cell_id),
+        };
+        if let Err(error) = result {
+            // A cell spawned outside the map, or into a map that's gone, has nowhere to go
+            if let CellError::OutOfBounds { .. } | CellError::MapMissing = error {
+                world.despawn(self.cell_id);
+            }
+            report_error::<L, N>(world, self.map_id, error);
+        }
     }
 }
 
 pub struct DespawnCell<L, const N: usize> {
+    pub map_id: Option<Entity>,
     pub cell_c: [isize; N],
+    pub fallible: bool,
     pub label: std::marker::PhantomData<L>,
 }
 
@@ -30,16 +55,31 @@ where
     L: CellMapLabel + Send + 'static,
 {
     fn apply(self, world: &mut World) {
-        let cell_id = take_cell::<L, N>(world, self.cell_c);
-        if let Some(id) = cell_id {
-            CheckedDespawn(id).apply(world);
+        let map_id = if let Some(map_id) = find_map::<L, N>(world, self.map_id) {
+            map_id
+        } else {
+            if self.fallible {
+                report_error::<L, N>(world, self.map_id, CellError::MapMissing);
+            }
+            return;
+        };
+
+        match try_take_cell::<L, N>(world, Some(map_id), self.cell_c) {
+            Ok(id) => {
+                CheckedDespawn(id).apply(world);
+                send_despawned_events::<L, N>(world, map_id, [(self.cell_c, id)]);
+            }
+            Err(error) if self.fallible => report_error::<L, N>(world, self.map_id, error),
+            Err(_) => {}
         }
     }
 }
 
 pub struct SwapCell<L, const N: usize> {
+    pub map_id: Option<Entity>,
     pub cell_c_1: [isize; N],
     pub cell_c_2: [isize; N],
+    pub fallible: bool,
     pub label: std::marker::PhantomData<L>,
 }
 
@@ -52,33 +92,78 @@ where
             return;
         }
 
-        let cell_id_1 = take_cell::<L, N>(world, self.cell_c_1);
+        let map_id = if let Some(map_id) = find_map::<L, N>(world, self.map_id) {
+            map_id
+        } else {
+            if self.fallible {
+                report_error::<L, N>(world, self.map_id, CellError::MapMissing);
+            }
+            return;
+        };
+
+        // Swapping can't clamp, since the cells have to trade places exactly
+        let config = map_config::<L, N>(world, map_id);
+        if let Some(cell_c) = [self.cell_c_1, self.cell_c_2]
+            .into_iter()
+            .find(|cell_c| !config.contains(*cell_c))
+        {
+            if self.fallible {
+                report_error::<L, N>(world, self.map_id, CellError::OutOfBounds { cell_c });
+            }
+            return;
+        }
+
+        let cell_id_1 = take_cell_inner::<L, N>(world, Some(map_id), self.cell_c_1)
+            .ok()
+            .map(|(_, cell_id)| cell_id);
 
-        let cell_id_2 = take_cell::<L, N>(world, self.cell_c_2);
+        let cell_id_2 = take_cell_inner::<L, N>(world, Some(map_id), self.cell_c_2)
+            .ok()
+            .map(|(_, cell_id)| cell_id);
 
+        if self.fallible && cell_id_1.is_none() && cell_id_2.is_none() {
+            let error = CellError::CellEmpty {
+                cell_c: self.cell_c_1,
+            };
+            report_error::<L, N>(world, self.map_id, error);
+            return;
+        }
+
+        let mut moved = Vec::new();
+
+        // Both coordinates are empty now, so these can't replace anything
         if let Some(cell_id) = cell_id_1 {
-            SpawnCell::<L, N> {
-                cell_c: self.cell_c_2,
+            let _ = insert_cell_inner::<L, N>(
+                world,
+                map_id,
+                self.cell_c_2,
                 cell_id,
-                label: self.label,
-            }
-            .apply(world);
+                Some(InsertPolicy::ReplaceDespawn),
+            );
+            moved.push((self.cell_c_1, self.cell_c_2, cell_id));
         }
 
         if let Some(cell_id) = cell_id_2 {
-            SpawnCell::<L, N> {
-                cell_c: self.cell_c_1,
+            let _ = insert_cell_inner::<L, N>(
+                world,
+                map_id,
+                self.cell_c_1,
                 cell_id,
-                label: self.label,
-            }
-            .apply(world);
+                Some(InsertPolicy::ReplaceDespawn),
+            );
+            moved.push((self.cell_c_2, self.cell_c_1, cell_id));
         }
+
+        despawn_empty_chunks::<L, N>(world, map_id, [self.cell_c_1, self.cell_c_2]);
+        send_moved_events::<L, N>(world, map_id, moved);
     }
 }
 
 pub struct MoveCell<L, const N: usize> {
+    pub map_id: Option<Entity>,
     pub old_c: [isize; N],
     pub new_c: [isize; N],
+    pub fallible: bool,
     pub label: std::marker::PhantomData<L>,
 }
 
@@ -91,15 +176,40 @@ where
             return;
         }
 
-        let old_cell_id = take_cell::<L, N>(world, self.old_c);
-
-        if let Some(old_cell_id) = old_cell_id {
-            SpawnCell::<L, N> {
-                cell_c: self.new_c,
-                cell_id: old_cell_id,
-                label: self.label,
+        let map_id = if let Some(map_id) = find_map::<L, N>(world, self.map_id) {
+            map_id
+        } else {
+            if self.fallible {
+                report_error::<L, N>(world, self.map_id, CellError::MapMissing);
+            }
+            return;
+        };
+
+        let new_c = match map_config::<L, N>(world, map_id).resolve(self.new_c) {
+            Ok(new_c) => new_c,
+            Err(error) => {
+                if self.fallible {
+                    report_error::<L, N>(world, self.map_id, error);
+                }
+                return;
             }
-            .apply(world);
+        };
+
+        // Fallible moves don't replace, so check before the cell is taken out of the map
+        if self.fallible && new_c != self.old_c {
+            if let Some(occupant) = cell_at::<L, N>(world, map_id, new_c) {
+                let error = CellError::Occupied {
+                    cell_c: new_c,
+                    occupant,
+                };
+                report_error::<L, N>(world, self.map_id, error);
+                return;
+            }
+        }
+
+        match move_cell::<L, N>(world, Some(map_id), self.old_c, new_c) {
+            Err(error) if self.fallible => report_error::<L, N>(world, self.map_id, error),
+            _ => {}
         }
     }
 }
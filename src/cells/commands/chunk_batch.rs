@@ -2,7 +2,7 @@ use bevy::ecs::{bundle::Bundle, entity::Entity, system::Command, world::World};
 
 use crate::prelude::CellMapLabel;
 
-use super::{insert_chunk_batch, take_chunk_batch_despawn_cells};
+use super::{insert_chunk_batch, report_error, take_chunk_batch_despawn_cells};
 
 pub struct SpawnChunkBatch<L, F, B, IC, const N: usize = 2>
 where
@@ -11,6 +11,7 @@ where
     B: Bundle + Send + 'static,
     IC: IntoIterator<Item = [isize; N]> + Send + 'static,
 {
+    pub map_id: Option<Entity>,
     pub chunk_cs: IC,
     pub bundle_f: F,
     pub label: std::marker::PhantomData<L>,
@@ -35,7 +36,16 @@ where
             .zip(world.spawn_batch(bundles))
             .collect::<Vec<([isize; N], Entity)>>();
 
-        insert_chunk_batch::<L, N>(world, chunks);
+        let chunk_ids = chunks
+            .iter()
+            .map(|(_, chunk_id)| *chunk_id)
+            .collect::<Vec<_>>();
+        if let Err(error) = insert_chunk_batch::<L, N>(world, self.map_id, chunks) {
+            for chunk_id in chunk_ids {
+                world.despawn(chunk_id);
+            }
+            report_error::<L, N>(world, self.map_id, error);
+        }
     }
 }
 
@@ -44,6 +54,7 @@ where
     L: CellMapLabel + Send + 'static,
     IC: IntoIterator<Item = [isize; N]> + Send + 'static,
 {
+    pub map_id: Option<Entity>,
     pub chunk_cs: IC,
     pub label: std::marker::PhantomData<L>,
 }
@@ -54,7 +65,9 @@ where
     IC: IntoIterator<Item = [isize; N]> + Send + 'static,
 {
     fn apply(self, world: &mut World) {
-        for (_, cell_id) in take_chunk_batch_despawn_cells::<L, N>(world, self.chunk_cs) {
+        for (_, cell_id) in
+            take_chunk_batch_despawn_cells::<L, N>(world, self.map_id, self.chunk_cs)
+        {
             world.despawn(cell_id);
         }
     }
@@ -1,11 +1,12 @@
 use aery::edges::CheckedDespawn;
 use bevy::ecs::{entity::Entity, system::Command, world::World};
 
-use crate::prelude::CellMapLabel;
+use crate::prelude::{CellError, CellMap, CellMapLabel};
 
-use super::{insert_chunk, take_chunk, take_chunk_despawn_cells};
+use super::{find_map, insert_chunk, report_error, take_chunk_despawn_cells};
 
 pub struct SpawnChunk<L, const N: usize = 2> {
+    pub map_id: Option<Entity>,
     pub chunk_c: [isize; N],
     pub chunk_id: Entity,
     pub label: std::marker::PhantomData<L>,
@@ -16,12 +17,17 @@ where
     L: CellMapLabel + Send + 'static,
 {
     fn apply(self, world: &mut World) {
-        insert_chunk::<L, N>(world, self.chunk_c, self.chunk_id)
+        if let Err(error) = insert_chunk::<L, N>(world, self.map_id, self.chunk_c, self.chunk_id) {
+            world.despawn(self.chunk_id);
+            report_error::<L, N>(world, self.map_id, error);
+        }
     }
 }
 
 pub struct DespawnChunk<L, const N: usize> {
+    pub map_id: Option<Entity>,
     pub chunk_c: [isize; N],
+    pub fallible: bool,
     pub label: std::marker::PhantomData<L>,
 }
 
@@ -30,7 +36,22 @@ where
     L: CellMapLabel + Send + 'static,
 {
     fn apply(self, world: &mut World) {
-        let cell_id = take_chunk_despawn_cells::<L, N>(world, self.chunk_c);
+        if self.fallible {
+            let Some(map_id) = find_map::<L, N>(world, self.map_id) else {
+                report_error::<L, N>(world, self.map_id, CellError::MapMissing);
+                return;
+            };
+            let map = world.get::<CellMap<L, N>>(map_id).unwrap();
+            if !map.chunks.contains_key(&self.chunk_c.into()) {
+                let error = CellError::ChunkMissing {
+                    chunk_c: self.chunk_c,
+                };
+                report_error::<L, N>(world, self.map_id, error);
+                return;
+            }
+        }
+
+        let cell_id = take_chunk_despawn_cells::<L, N>(world, self.map_id, self.chunk_c);
         if let Some(id) = cell_id {
             CheckedDespawn(id).apply(world);
         }
@@ -1,9 +1,12 @@
 use aery::edges::CheckedDespawn;
-use bevy::ecs::{entity::Entity, query::With, system::Command, world::World};
+use bevy::ecs::{entity::Entity, event::Events, system::Command, world::World};
 
-use crate::prelude::{CellMap, CellMapLabel};
+use crate::prelude::{CellCoord, CellDespawned, CellMap, CellMapLabel, Chunk};
+
+use super::{find_map, send_despawned_events};
 
 pub struct DespawnMap<L, const N: usize = 2> {
+    pub map_id: Option<Entity>,
     pub label: std::marker::PhantomData<L>,
 }
 
@@ -12,11 +15,32 @@ where
     L: CellMapLabel + Send + 'static,
 {
     fn apply(self, world: &mut World) {
-        if let Ok(map_id) = world
-            .query_filtered::<Entity, With<CellMap<L, N>>>()
-            .get_single(world)
-        {
-            CheckedDespawn(map_id).apply(world);
-        }
+        let map_id = if let Some(map_id) = find_map::<L, N>(world, self.map_id) {
+            map_id
+        } else {
+            return;
+        };
+
+        // Only gather the despawned cells if someone is listening for them
+        let despawned = if world.contains_resource::<Events<CellDespawned<L, N>>>() {
+            world
+                .get::<CellMap<L, N>>(map_id)
+                .unwrap()
+                .chunks
+                .values()
+                .filter_map(|chunk_id| world.get::<Chunk>(*chunk_id))
+                .flat_map(|chunk| chunk.cells.iter().flatten())
+                .filter_map(|cell_id| {
+                    world
+                        .get::<CellCoord<N>>(*cell_id)
+                        .map(|cell_c| (**cell_c, *cell_id))
+                })
+                .collect::<Vec<([isize; N], Entity)>>()
+        } else {
+            Vec::new()
+        };
+
+        CheckedDespawn(map_id).apply(world);
+        send_despawned_events::<L, N>(world, map_id, despawned);
     }
 }
@@ -0,0 +1,233 @@
+use std::marker::PhantomData;
+
+use aery::edges::CheckedDespawn;
+use bevy::ecs::{bundle::Bundle, entity::Entity, system::Command, world::World};
+
+use crate::prelude::{CellError, CellMapConfig, CellMapLabel, InsertOutcome, InsertPolicy};
+
+use super::{
+    cell_at, find_map, insert_cell, insert_cell_with_policy, map_config, move_cell,
+    send_despawned_events, try_take_cell,
+};
+
+/// Applies changes to a specific cell map immediately, for exclusive systems and tests.
+pub struct CellWorld<'a, L, const N: usize> {
+    world: &'a mut World,
+    map_id: Option<Entity>,
+    phantom: PhantomData<L>,
+}
+
+pub trait CellWorldExt {
+    /// Gets the [CellWorld] to change cells at the cell map level.
+    fn cells<L, const N: usize>(&mut self) -> CellWorld<'_, L, N>
+    where
+        L: CellMapLabel + 'static;
+
+    /// Gets the [CellWorld] to change cells in a specific cell map entity.
+    /// # Note
+    /// Use this when multiple maps share the same [CellMapLabel].
+    fn cells_on<L, const N: usize>(&mut self, map_id: Entity) -> CellWorld<'_, L, N>
+    where
+        L: CellMapLabel + 'static;
+}
+
+impl CellWorldExt for World {
+    fn cells<L, const N: usize>(&mut self) -> CellWorld<'_, L, N>
+    where
+        L: CellMapLabel + 'static,
+    {
+        CellWorld {
+            world: self,
+            map_id: None,
+            phantom: PhantomData,
+        }
+    }
+
+    fn cells_on<L, const N: usize>(&mut self, map_id: Entity) -> CellWorld<'_, L, N>
+    where
+        L: CellMapLabel + 'static,
+    {
+        CellWorld {
+            world: self,
+            map_id: Some(map_id),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, L, const N: usize> CellWorld<'a, L, N>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    /// Gets the map entity, if the map exists.
+    pub fn map_id(&mut self) -> Option<Entity> {
+        find_map::<L, N>(self.world, self.map_id)
+    }
+
+    /// Gets the settings of the map, or the defaults from the label if the map doesn't exist yet.
+    pub fn config(&mut self) -> CellMapConfig<L, N> {
+        match self.map_id() {
+            Some(map_id) => map_config::<L, N>(self.world, map_id),
+            None => CellMapConfig::default(),
+        }
+    }
+
+    /// Gets the cell at a coordinate.
+    pub fn get_at(&mut self, cell_c: [isize; N]) -> Option<Entity> {
+        let map_id = self.map_id()?;
+        cell_at::<L, N>(self.world, map_id, cell_c)
+    }
+
+    /// Spawns a cell and returns the entity, following the [InsertPolicy] of the map
+    /// if the coordinate already has a cell.
+    /// # Note
+    /// If the policy keeps the old cell, the new cell is despawned and [CellError::Occupied] is returned.
+    pub fn spawn_cell<T>(&mut self, cell_c: [isize; N], bundle: T) -> Result<Entity, CellError<N>>
+    where
+        T: Bundle,
+    {
+        let policy = self.config().insert_policy();
+        self.spawn_cell_with_policy(cell_c, bundle, policy)
+    }
+
+    /// Spawns a cell and returns the entity, following the given policy if the coordinate already has a cell.
+    /// # Note
+    /// See [spawn_cell](CellWorld::spawn_cell) for what happens when the old cell is kept.
+    pub fn spawn_cell_with_policy<T>(
+        &mut self,
+        cell_c: [isize; N],
+        bundle: T,
+        policy: InsertPolicy,
+    ) -> Result<Entity, CellError<N>>
+    where
+        T: Bundle,
+    {
+        let cell_id = self.world.spawn(bundle).id();
+        // Report the coordinate the cell was headed for after the bounds of the map
+        let result = self.config().resolve(cell_c).and_then(|cell_c| {
+            Ok((
+                cell_c,
+                self.insert_cell_with_policy(cell_c, cell_id, policy)?,
+            ))
+        });
+        match result {
+            Ok((cell_c, InsertOutcome::Rejected(_))) => {
+                let occupant = self.get_at(cell_c).ok_or(CellError::CellEmpty { cell_c })?;
+                Err(CellError::Occupied { cell_c, occupant })
+            }
+            Ok(_) => Ok(cell_id),
+            Err(error) => {
+                self.world.despawn(cell_id);
+                Err(error)
+            }
+        }
+    }
+
+    /// Inserts an existing entity as a cell, following the [InsertPolicy] of the map
+    /// if the coordinate already has a cell.
+    pub fn insert_cell(
+        &mut self,
+        cell_c: [isize; N],
+        cell_id: Entity,
+    ) -> Result<InsertOutcome, CellError<N>> {
+        insert_cell::<L, N>(self.world, self.map_id, cell_c, cell_id)
+    }
+
+    /// Inserts an existing entity as a cell, following the given policy if the coordinate already has a cell.
+    pub fn insert_cell_with_policy(
+        &mut self,
+        cell_c: [isize; N],
+        cell_id: Entity,
+        policy: InsertPolicy,
+    ) -> Result<InsertOutcome, CellError<N>> {
+        insert_cell_with_policy::<L, N>(self.world, self.map_id, cell_c, cell_id, policy)
+    }
+
+    /// Takes a cell out of the map without despawning it.
+    pub fn take_cell(&mut self, cell_c: [isize; N]) -> Result<Entity, CellError<N>> {
+        try_take_cell::<L, N>(self.world, self.map_id, cell_c)
+    }
+
+    /// Despawns a cell, returning the despawned entity.
+    pub fn despawn_cell(&mut self, cell_c: [isize; N]) -> Result<Entity, CellError<N>> {
+        let map_id = self.map_id().ok_or(CellError::MapMissing)?;
+        let cell_id = try_take_cell::<L, N>(self.world, Some(map_id), cell_c)?;
+        CheckedDespawn(cell_id).apply(self.world);
+        send_despawned_events::<L, N>(self.world, map_id, [(cell_c, cell_id)]);
+        Ok(cell_id)
+    }
+
+    /// Moves a cell from one coordinate to another, overwriting and despawning any cell in the new coordinate.
+    /// Returns the moved cell.
+    pub fn move_cell(
+        &mut self,
+        old_c: [isize; N],
+        new_c: [isize; N],
+    ) -> Result<Entity, CellError<N>> {
+        move_cell::<L, N>(self.world, self.map_id, old_c, new_c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cells::{BoundsPolicy, CellCoord, CellMap};
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 16;
+    }
+
+    #[test]
+    fn immediate_changes() {
+        let mut world = World::new();
+        let mut cells = world.cells::<TestLayer, 2>();
+
+        let cell = cells.spawn_cell([1, 2], ()).unwrap();
+        assert_eq!(cells.get_at([1, 2]), Some(cell));
+
+        assert_eq!(cells.move_cell([1, 2], [-30, 2]), Ok(cell));
+        assert_eq!(cells.get_at([1, 2]), None);
+        assert_eq!(cells.get_at([-30, 2]), Some(cell));
+
+        let other = cells
+            .spawn_cell_with_policy([-30, 2], (), InsertPolicy::Reject)
+            .unwrap_err();
+        assert_eq!(
+            other,
+            CellError::Occupied {
+                cell_c: [-30, 2],
+                occupant: cell
+            }
+        );
+
+        assert_eq!(cells.despawn_cell([-30, 2]), Ok(cell));
+        assert_eq!(
+            cells.despawn_cell([-30, 2]),
+            Err(CellError::CellEmpty { cell_c: [-30, 2] })
+        );
+        assert!(world.get_entity(cell).is_none());
+    }
+
+    #[test]
+    fn clamped_spawns_report_the_occupied_cell() {
+        let mut world = World::new();
+        let config = CellMapConfig::<TestLayer>::default()
+            .with_bounds([0, 0], [9, 9])
+            .with_bounds_policy(BoundsPolicy::Clamp);
+        let map_id = world.spawn(CellMap::new(config)).id();
+        let mut cells = world.cells_on::<TestLayer, 2>(map_id);
+
+        let cell = cells.spawn_cell([12, 5], ()).unwrap();
+        assert_eq!(cells.get_at([9, 5]), Some(cell));
+        assert_eq!(
+            cells.spawn_cell_with_policy([15, 5], (), InsertPolicy::Reject),
+            Err(CellError::Occupied {
+                cell_c: [9, 5],
+                occupant: cell
+            })
+        );
+        assert_eq!(world.query::<&CellCoord>().iter(&world).count(), 1);
+    }
+}
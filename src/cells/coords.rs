@@ -1,10 +1,12 @@
+pub mod hex;
+
 #[inline]
 pub fn calculate_chunk_coordinate<const N: usize>(
     mut cell_c: [isize; N],
-    chunk_size: usize,
+    chunk_size: [usize; N],
 ) -> [isize; N] {
-    for i in cell_c.iter_mut() {
-        *i = *i / (chunk_size as isize) - if *i < 0 { 1 } else { 0 }
+    for (i, size) in cell_c.iter_mut().zip(chunk_size) {
+        *i = *i / (size as isize) - if *i < 0 { 1 } else { 0 }
     }
     cell_c
 }
@@ -12,23 +14,25 @@ pub fn calculate_chunk_coordinate<const N: usize>(
 #[inline]
 pub fn calculate_chunk_relative_cell_coordinate<const N: usize>(
     mut cell_c: [isize; N],
-    chunk_size: usize,
+    chunk_size: [usize; N],
 ) -> [isize; N] {
-    for i in cell_c.iter_mut() {
-        *i %= chunk_size as isize;
+    for (i, size) in cell_c.iter_mut().zip(chunk_size) {
+        *i %= size as isize;
         if *i < 0 {
-            *i += chunk_size as isize;
+            *i += size as isize;
         }
     }
     cell_c
 }
 
 #[inline]
-pub fn calculate_cell_index<const N: usize>(cell_c: [isize; N], chunk_size: usize) -> usize {
+pub fn calculate_cell_index<const N: usize>(cell_c: [isize; N], chunk_size: [usize; N]) -> usize {
     let mut index = 0;
+    let mut stride = 1;
     let relative_cell_c = calculate_chunk_relative_cell_coordinate(cell_c, chunk_size);
-    for (i, c) in relative_cell_c.iter().enumerate() {
-        index += (*c as usize) * chunk_size.pow(i as u32);
+    for (c, size) in relative_cell_c.iter().zip(chunk_size) {
+        index += (*c as usize) * stride;
+        stride *= size;
     }
     index
 }
@@ -37,26 +41,50 @@ pub fn calculate_cell_index<const N: usize>(cell_c: [isize; N], chunk_size: usiz
 pub fn calculate_cell_coordinate<const N: usize>(
     chunk_c: [isize; N],
     cell_i: usize,
-    chunk_size: usize,
+    chunk_size: [usize; N],
 ) -> [isize; N] {
-    let mut chunk_world_c = chunk_c.map(|c| c * chunk_size as isize);
-    for (i, c) in chunk_world_c.iter_mut().enumerate() {
-        if i == 0 {
-            *c += (cell_i % chunk_size) as isize;
+    let mut chunk_world_c = chunk_c;
+    let mut stride = 1;
+    for (c, size) in chunk_world_c.iter_mut().zip(chunk_size) {
+        let relative_c = ((cell_i / stride) % size) as isize;
+        // Negative multiples of the size belong to the chunk below, see calculate_chunk_coordinate
+        *c = if *c < 0 && relative_c == 0 {
+            (*c + 1) * size as isize
         } else {
-            *c += (cell_i / chunk_size.pow(i as u32)) as isize;
-        }
+            *c * size as isize + relative_c
+        };
+        stride *= size;
     }
     chunk_world_c
 }
 
+/// Gets the lowest and highest cell coordinates that fall into a chunk.
+/// # Note
+/// This mirrors the rounding of negative cells in [calculate_chunk_coordinate],
+/// so the chunks just below zero are one cell short along that axis.
 #[inline]
-pub fn max_cell_index<const N: usize>(chunk_size: usize) -> usize {
-    let mut index = 0;
-    for i in 1..=N {
-        index += chunk_size.pow(i as u32);
+pub fn calculate_chunk_cell_range<const N: usize>(
+    chunk_c: [isize; N],
+    chunk_size: [usize; N],
+) -> ([isize; N], [isize; N]) {
+    let mut min = chunk_c;
+    let mut max = chunk_c;
+    for i in 0..N {
+        let size = chunk_size[i] as isize;
+        if chunk_c[i] >= 0 {
+            min[i] = chunk_c[i] * size;
+            max[i] = min[i] + size - 1;
+        } else {
+            min[i] = chunk_c[i] * size + 1;
+            max[i] = ((chunk_c[i] + 1) * size).min(-1);
+        }
     }
-    index - 1
+    (min, max)
+}
+
+#[inline]
+pub fn max_cell_index<const N: usize>(chunk_size: [usize; N]) -> usize {
+    chunk_size.iter().product::<usize>() - 1
 }
 
 /// Calculate the cell coordinate given a world coordinate
@@ -65,7 +93,7 @@ pub fn max_cell_index<const N: usize>(chunk_size: usize) -> usize {
 /// the scale factor should be set to 16)
 #[inline]
 pub fn world_to_cell<const N: usize>(world_c: [f32; N], scale_f: f32) -> [isize; N] {
-    world_c.map(|c| (c / scale_f - if c < 0.0 { 1.0 } else { 0.0 }) as isize)
+    world_c.map(|c| (c / scale_f).floor() as isize)
 }
 
 pub struct CoordIterator<const N: usize> {
@@ -120,6 +148,161 @@ impl<const N: usize> Iterator for CoordIterator<N> {
     }
 }
 
+/// Iterates over every cell crossed by a ray, in order, using the Amanatides & Woo traversal.
+/// # Note
+/// Rays are given in cell space, where cell `c` covers `c` inclusive to `c + 1` exclusive on each axis.
+/// Divide world coordinates by the cell size before casting (see [world_to_cell]),
+/// this matches square axes of a [GridGeometry](super::transform::GridGeometry) relative to its origin.
+pub struct GridRay<const N: usize> {
+    cell_c: [isize; N],
+    step: [isize; N],
+    t_max: [f32; N],
+    t_delta: [f32; N],
+    max_dist: f32,
+    distance: f32,
+    normal: [isize; N],
+    started: bool,
+}
+
+/// A cell crossed by a [GridRay].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridRayHit<const N: usize> {
+    pub cell_c: [isize; N],
+    /// The normal of the face the ray entered the cell through,
+    /// all zeros for the cell the ray starts in.
+    pub normal: [isize; N],
+    /// The distance along the ray to where it entered the cell.
+    pub distance: f32,
+}
+
+impl<const N: usize> GridRay<N> {
+    /// Creates a ray from `origin` in the direction of `dir`, which doesn't need to be normalized,
+    /// that ends after `max_dist`.
+    pub fn new(origin: [f32; N], dir: [f32; N], max_dist: f32) -> Self {
+        let length = dir.iter().map(|d| d * d).sum::<f32>().sqrt();
+        let mut ray = Self {
+            cell_c: origin.map(|c| c.floor() as isize),
+            step: [0; N],
+            t_max: [f32::INFINITY; N],
+            t_delta: [f32::INFINITY; N],
+            max_dist,
+            distance: 0.0,
+            normal: [0; N],
+            started: false,
+        };
+        if length == 0.0 {
+            return ray;
+        }
+
+        for i in 0..N {
+            let dir = dir[i] / length;
+            let offset = origin[i] - origin[i].floor();
+            if dir > 0.0 {
+                ray.step[i] = 1;
+                ray.t_max[i] = (1.0 - offset) / dir;
+                ray.t_delta[i] = 1.0 / dir;
+            } else if dir < 0.0 {
+                ray.step[i] = -1;
+                ray.t_max[i] = offset / -dir;
+                ray.t_delta[i] = 1.0 / -dir;
+            }
+        }
+        ray
+    }
+
+    /// Gets the cell last returned by the ray along with how the ray entered it.
+    #[inline]
+    pub fn hit(&self) -> GridRayHit<N> {
+        GridRayHit {
+            cell_c: self.cell_c,
+            normal: self.normal,
+            distance: self.distance,
+        }
+    }
+
+    /// Steps the ray into the next cell, returning false if that would go past the end of the ray.
+    #[inline]
+    fn advance(&mut self) -> bool {
+        let Some(axis) = (0..N).min_by(|a, b| self.t_max[*a].total_cmp(&self.t_max[*b])) else {
+            return false;
+        };
+        if !self.t_max[axis].is_finite() || self.t_max[axis] > self.max_dist {
+            return false;
+        }
+
+        self.distance = self.t_max[axis];
+        self.cell_c[axis] += self.step[axis];
+        self.t_max[axis] += self.t_delta[axis];
+        self.normal = [0; N];
+        self.normal[axis] = -self.step[axis];
+        true
+    }
+}
+
+impl<const N: usize> Iterator for GridRay<N> {
+    type Item = [isize; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            return Some(self.cell_c);
+        }
+        self.advance().then_some(self.cell_c)
+    }
+}
+
+/// Describes which cells are considered adjacent to a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Adjacency {
+    /// Cells that share a face with the cell (4 in 2d, 6 in 3d).
+    Face,
+    /// Cells that share a face or an edge with the cell (8 in 2d, 18 in 3d).
+    Edge,
+    /// Cells that share a face, an edge or a corner with the cell (8 in 2d, 26 in 3d).
+    Corner,
+    /// Cells that are offset by one from the cell along at most the given number of axes.
+    Axes(usize),
+}
+
+impl Adjacency {
+    /// The von Neumann neighborhood, an alias for [Adjacency::Face].
+    pub const VON_NEUMANN: Self = Self::Face;
+    /// The Moore neighborhood, an alias for [Adjacency::Corner].
+    pub const MOORE: Self = Self::Corner;
+
+    /// The maximum number of axes a neighbor can be offset along.
+    #[inline]
+    pub fn max_axes<const N: usize>(&self) -> usize {
+        match self {
+            Adjacency::Face => 1,
+            Adjacency::Edge => 2,
+            Adjacency::Corner => N,
+            Adjacency::Axes(axes) => *axes,
+        }
+    }
+}
+
+/// Iterate over the coordinates adjacent to the given cell coordinate.
+#[inline]
+pub fn neighbors<const N: usize>(
+    cell_c: [isize; N],
+    adjacency: Adjacency,
+) -> impl Iterator<Item = [isize; N]> {
+    let max_axes = adjacency.max_axes::<N>();
+    CoordIterator::new([-1; N], [1; N]).filter_map(move |offset| {
+        let axes = offset.iter().filter(|o| **o != 0).count();
+        if axes == 0 || axes > max_axes {
+            return None;
+        }
+        let mut neighbor_c = cell_c;
+        for (c, o) in neighbor_c.iter_mut().zip(offset) {
+            *c += o;
+        }
+        Some(neighbor_c)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -161,16 +344,82 @@ mod tests {
     }
 
     #[rstest]
-    #[case(16, [15, 0], 15)]
-    #[case(16, [0, 15], 240)]
-    #[case(16, [15, 15], 255)]
-    #[case(16, [-1, -1], 255)]
-    #[case(16, [-16, -16], 0)]
+    #[case([16, 16], [15, 0], 15)]
+    #[case([16, 16], [0, 15], 240)]
+    #[case([16, 16], [15, 15], 255)]
+    #[case([16, 16], [-1, -1], 255)]
+    #[case([16, 16], [-16, -16], 0)]
+    #[case([64, 16], [63, 0], 63)]
+    #[case([64, 16], [0, 15], 960)]
+    #[case([64, 16], [-1, -1], 1023)]
     fn cell_index_test(
-        #[case] chunk_size: usize,
+        #[case] chunk_size: [usize; 2],
         #[case] cell_c: [isize; 2],
         #[case] index: usize,
     ) {
         assert_eq!(calculate_cell_index(cell_c, chunk_size), index)
     }
+
+    #[rstest]
+    #[case([16, 16, 256], [3, 7, 200])]
+    #[case([16, 16, 256], [15, 0, 255])]
+    #[case([64, 16, 1], [40, -3, 9])]
+    #[case([16, 16, 256], [-16, -32, -256])]
+    #[case([16, 16, 256], [-1, -15, -17])]
+    #[case([64, 16, 1], [-64, -16, -5])]
+    fn cell_coordinate_roundtrip(#[case] chunk_size: [usize; 3], #[case] cell_c: [isize; 3]) {
+        let chunk_c = calculate_chunk_coordinate(cell_c, chunk_size);
+        let cell_i = calculate_cell_index(cell_c, chunk_size);
+        assert!(cell_i <= max_cell_index(chunk_size));
+        assert_eq!(
+            calculate_cell_coordinate(chunk_c, cell_i, chunk_size),
+            cell_c
+        );
+    }
+
+    #[rstest]
+    #[case(Adjacency::Face, 4)]
+    #[case(Adjacency::Edge, 8)]
+    #[case(Adjacency::Corner, 8)]
+    #[case(Adjacency::Axes(0), 0)]
+    fn neighbors_2d(#[case] adjacency: Adjacency, #[case] count: usize) {
+        let neighbors = neighbors([3, -3], adjacency).collect::<Vec<[isize; 2]>>();
+        assert_eq!(neighbors.len(), count);
+        assert!(!neighbors.contains(&[3, -3]));
+    }
+
+    #[rstest]
+    #[case(Adjacency::VON_NEUMANN, 6)]
+    #[case(Adjacency::Edge, 18)]
+    #[case(Adjacency::MOORE, 26)]
+    fn neighbors_3d(#[case] adjacency: Adjacency, #[case] count: usize) {
+        assert_eq!(neighbors([0, 0, 0], adjacency).count(), count);
+    }
+
+    #[rstest]
+    #[case([0.5, 0.5], [1.0, 0.0], 2.0, vec![[0, 0], [1, 0], [2, 0]])]
+    #[case([0.5, 0.5], [-1.0, -1.0], 1.0, vec![[0, 0], [-1, 0], [-1, -1]])]
+    #[case([-0.5, 0.25], [0.0, 1.0], 0.5, vec![[-1, 0]])]
+    #[case([0.5, 0.5], [0.0, 0.0], 10.0, vec![[0, 0]])]
+    fn grid_ray(
+        #[case] origin: [f32; 2],
+        #[case] dir: [f32; 2],
+        #[case] max_dist: f32,
+        #[case] cells: Vec<[isize; 2]>,
+    ) {
+        assert_eq!(
+            GridRay::new(origin, dir, max_dist).collect::<Vec<_>>(),
+            cells
+        );
+    }
+
+    #[test]
+    fn grid_ray_hit() {
+        let mut ray = GridRay::new([0.5, 0.5, 0.5], [0.0, 0.0, -1.0], 10.0);
+        ray.nth(2);
+        let hit = ray.hit();
+        assert_eq!(hit.cell_c, [0, 0, -2]);
+        assert_eq!(hit.normal, [0, 0, 1]);
+        assert_eq!(hit.distance, 1.5);
+    }
 }
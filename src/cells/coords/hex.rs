@@ -0,0 +1,251 @@
+use std::ops::{Add, Mul, Sub};
+
+const SQRT_3: f32 = 1.732_050_8;
+
+/// An axial hex coordinate, stored in a cell map as `[q, r]`.
+/// # Note
+/// The third cube coordinate `s` is implied, since `q + r + s == 0`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl Hex {
+    /// The six directions around a hex, in counter clockwise order starting from `+q`.
+    pub const DIRECTIONS: [Hex; 6] = [
+        Hex::new(1, 0),
+        Hex::new(1, -1),
+        Hex::new(0, -1),
+        Hex::new(-1, 0),
+        Hex::new(-1, 1),
+        Hex::new(0, 1),
+    ];
+
+    pub const fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    /// Gets the implied third cube coordinate.
+    #[inline]
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    /// Creates a hex from cube coordinates `[q, r, s]`, ignoring `s`.
+    #[inline]
+    pub fn from_cube(cube_c: [isize; 3]) -> Self {
+        Self::new(cube_c[0], cube_c[1])
+    }
+
+    /// Gets the cube coordinates `[q, r, s]` of the hex.
+    #[inline]
+    pub fn to_cube(self) -> [isize; 3] {
+        [self.q, self.r, self.s()]
+    }
+
+    /// Gets the neighboring hex in one of the [Hex::DIRECTIONS].
+    #[inline]
+    pub fn neighbor(self, direction: usize) -> Self {
+        self + Self::DIRECTIONS[direction % 6]
+    }
+
+    /// Iterate over the six hexes around this one.
+    #[inline]
+    pub fn neighbors(self) -> impl Iterator<Item = Hex> {
+        Self::DIRECTIONS.into_iter().map(move |dir| self + dir)
+    }
+
+    /// Gets the number of steps between two hexes.
+    #[inline]
+    pub fn distance(self, other: Hex) -> usize {
+        let diff = self - other;
+        diff.q
+            .unsigned_abs()
+            .max(diff.r.unsigned_abs())
+            .max(diff.s().unsigned_abs())
+    }
+
+    /// Iterate over the hexes exactly `radius` steps away, going counter clockwise.
+    pub fn ring(self, radius: usize) -> impl Iterator<Item = Hex> {
+        let count = if radius == 0 { 1 } else { 6 * radius };
+        let radius = radius as isize;
+        (0..count).map(move |n| {
+            if radius == 0 {
+                return self;
+            }
+            let side = n / radius as usize;
+            let step = n as isize % radius;
+            self + Self::DIRECTIONS[(side + 4) % 6] * radius + Self::DIRECTIONS[side] * step
+        })
+    }
+
+    /// Iterate over the hexes at most `radius` steps away, ring by ring from the center.
+    pub fn spiral(self, radius: usize) -> impl Iterator<Item = Hex> {
+        (0..=radius).flat_map(move |radius| self.ring(radius))
+    }
+
+    /// Rounds fractional axial coordinates to the nearest hex.
+    pub fn round(q: f32, r: f32) -> Self {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Self::new(rq as isize, rr as isize)
+    }
+
+    /// Converts the hex into an offset coordinate `[col, row]`.
+    pub fn to_offset(self, layout: OffsetLayout) -> [isize; 2] {
+        let Hex { q, r } = self;
+        match layout {
+            OffsetLayout::OddR => [q + (r - (r & 1)) / 2, r],
+            OffsetLayout::EvenR => [q + (r + (r & 1)) / 2, r],
+            OffsetLayout::OddQ => [q, r + (q - (q & 1)) / 2],
+            OffsetLayout::EvenQ => [q, r + (q + (q & 1)) / 2],
+        }
+    }
+
+    /// Creates a hex from an offset coordinate `[col, row]`.
+    pub fn from_offset(offset_c: [isize; 2], layout: OffsetLayout) -> Self {
+        let [col, row] = offset_c;
+        match layout {
+            OffsetLayout::OddR => Self::new(col - (row - (row & 1)) / 2, row),
+            OffsetLayout::EvenR => Self::new(col - (row + (row & 1)) / 2, row),
+            OffsetLayout::OddQ => Self::new(col, row - (col - (col & 1)) / 2),
+            OffsetLayout::EvenQ => Self::new(col, row - (col + (col & 1)) / 2),
+        }
+    }
+}
+
+impl From<[isize; 2]> for Hex {
+    fn from(value: [isize; 2]) -> Self {
+        Self::new(value[0], value[1])
+    }
+}
+
+impl From<Hex> for [isize; 2] {
+    fn from(value: Hex) -> Self {
+        [value.q, value.r]
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl Mul<isize> for Hex {
+    type Output = Hex;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+/// Which way the hexes in a grid are pointing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HexOrientation {
+    /// Hexes have a point at the top, and rows line up horizontally.
+    #[default]
+    Pointy,
+    /// Hexes have a flat top, and columns line up vertically.
+    Flat,
+}
+
+/// Offset layouts that store hexes in a rectangular grid by shoving every other row or column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OffsetLayout {
+    /// Odd rows are shoved right, for pointy hexes.
+    OddR,
+    /// Even rows are shoved right, for pointy hexes.
+    EvenR,
+    /// Odd columns are shoved down, for flat hexes.
+    OddQ,
+    /// Even columns are shoved down, for flat hexes.
+    EvenQ,
+}
+
+/// Calculate the world coordinate of a hex's center, given the size (center to corner) of a hex.
+/// # Note
+/// The world `y` axis grows along with `r`.
+#[inline]
+pub fn hex_to_world(hex: Hex, size: f32, orientation: HexOrientation) -> [f32; 2] {
+    let (q, r) = (hex.q as f32, hex.r as f32);
+    match orientation {
+        HexOrientation::Pointy => [size * SQRT_3 * (q + r / 2.0), size * 1.5 * r],
+        HexOrientation::Flat => [size * 1.5 * q, size * SQRT_3 * (r + q / 2.0)],
+    }
+}
+
+/// Calculate the hex containing a world coordinate, given the size (center to corner) of a hex.
+#[inline]
+pub fn world_to_hex(world_c: [f32; 2], size: f32, orientation: HexOrientation) -> Hex {
+    let [x, y] = world_c.map(|c| c / size);
+    match orientation {
+        HexOrientation::Pointy => Hex::round(SQRT_3 / 3.0 * x - y / 3.0, 2.0 / 3.0 * y),
+        HexOrientation::Flat => Hex::round(2.0 / 3.0 * x, SQRT_3 / 3.0 * y - x / 3.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Hex::new(0, 0), Hex::new(0, 0), 0)]
+    #[case(Hex::new(0, 0), Hex::new(2, -1), 2)]
+    #[case(Hex::new(-1, 3), Hex::new(2, -1), 4)]
+    fn hex_distance(#[case] hex_1: Hex, #[case] hex_2: Hex, #[case] distance: usize) {
+        assert_eq!(hex_1.distance(hex_2), distance);
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 6)]
+    #[case(3, 18)]
+    fn hex_ring(#[case] radius: usize, #[case] count: usize) {
+        let center = Hex::new(2, -5);
+        let ring = center.ring(radius).collect::<Vec<_>>();
+        assert_eq!(ring.len(), count);
+        assert!(ring.iter().all(|hex| hex.distance(center) == radius));
+        assert_eq!(center.spiral(2).count(), 19);
+    }
+
+    #[rstest]
+    #[case(OffsetLayout::OddR)]
+    #[case(OffsetLayout::EvenR)]
+    #[case(OffsetLayout::OddQ)]
+    #[case(OffsetLayout::EvenQ)]
+    fn offset_round_trip(#[case] layout: OffsetLayout) {
+        for hex in Hex::new(0, 0).spiral(3) {
+            assert_eq!(Hex::from_offset(hex.to_offset(layout), layout), hex);
+        }
+    }
+
+    #[rstest]
+    #[case(HexOrientation::Pointy)]
+    #[case(HexOrientation::Flat)]
+    fn world_round_trip(#[case] orientation: HexOrientation) {
+        for hex in Hex::new(1, -2).spiral(3) {
+            let [x, y] = hex_to_world(hex, 16.0, orientation);
+            assert_eq!(world_to_hex([x + 3.0, y - 3.0], 16.0, orientation), hex);
+        }
+    }
+}
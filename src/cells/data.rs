@@ -0,0 +1,456 @@
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    system::{Query, SystemParam},
+};
+
+use super::{
+    coords::{
+        calculate_cell_index, calculate_chunk_cell_range, calculate_chunk_coordinate, CoordIterator,
+    },
+    error::CellError,
+    CellMap, CellMapLabel,
+};
+
+/// A dense layer of plain values stored on each chunk entity, parallel to the cells in the chunk.
+/// # Note
+/// Use this for data every cell in a chunk has, like terrain or light levels, without spawning an entity per cell.
+/// Chunks only get a layer once a value is set in them, see
+/// [set_cell_data](super::commands::CellCommands::set_cell_data).
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct CellData<T> {
+    pub(crate) data: Vec<T>,
+}
+
+/// Marks a chunk that carries at least one [CellData] layer.
+/// # Note
+/// Chunks with this marker are kept when their last cell is taken out,
+/// even if the label despawns empty chunks, so their data isn't lost.
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct HasCellData;
+
+impl<T> CellData<T>
+where
+    T: Default + Clone,
+{
+    pub(crate) fn new(chunk_size: usize) -> Self {
+        Self {
+            data: vec![T::default(); chunk_size],
+        }
+    }
+}
+
+impl<T> CellData<T> {
+    /// Gets the value at a cell index in the chunk.
+    #[inline]
+    pub fn get(&self, cell_i: usize) -> Option<&T> {
+        self.data.get(cell_i)
+    }
+
+    /// Gets the value at a cell index in the chunk.
+    #[inline]
+    pub fn get_mut(&mut self, cell_i: usize) -> Option<&mut T> {
+        self.data.get_mut(cell_i)
+    }
+
+    /// Gets the values of every cell in the chunk, in cell index order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+}
+
+/// Used to read the [CellData] layer of type `T` from a cell map.
+#[derive(SystemParam)]
+pub struct CellDataQuery<'w, 's, L, T, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+    T: Send + Sync + 'static,
+{
+    data_q: Query<'w, 's, &'static CellData<T>>,
+    map_q: Query<'w, 's, &'static CellMap<L, N>>,
+}
+
+impl<'w, 's, L, T, const N: usize> CellDataQuery<'w, 's, L, T, N>
+where
+    L: CellMapLabel + 'static,
+    T: Send + Sync + 'static,
+{
+    /// Gets the value at the given cell.
+    /// # Note
+    /// If no map entity is given, the single map for the label is used.
+    pub fn get_on(&self, map_id: Option<Entity>, cell_c: [isize; N]) -> Option<&T> {
+        let (_, chunk_id, cell_i) = data_index::<L, N>(&self.map_q, map_id, cell_c).ok()?;
+        self.data_q.get(chunk_id).ok()?.get(cell_i)
+    }
+
+    /// Gets the value at the given cell.
+    pub fn get(&self, cell_c: [isize; N]) -> Option<&T> {
+        self.get_on(None, cell_c)
+    }
+
+    /// Iterate over the values in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`, skipping cells in chunks without the layer.
+    /// # Note
+    /// If no map entity is given, the single map for the label is used.
+    /// Values are visited chunk by chunk.
+    pub fn iter_in_on(
+        &self,
+        map_id: Option<Entity>,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> impl Iterator<Item = ([isize; N], &T)> + '_ {
+        let map = data_map::<L, N>(&self.map_q, map_id).ok();
+        map.into_iter().flat_map(move |map| {
+            let chunk_size = map.config.chunk_size();
+            chunks_in(map, corner_1, corner_2)
+                .filter_map(|(chunk_id, cells)| Some((self.data_q.get(chunk_id).ok()?, cells)))
+                .flat_map(move |(data, cells)| {
+                    cells.filter_map(move |cell_c| {
+                        Some((cell_c, data.get(calculate_cell_index(cell_c, chunk_size))?))
+                    })
+                })
+        })
+    }
+
+    /// Iterate over the values in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`, skipping cells in chunks without the layer.
+    /// # Note
+    /// Values are visited chunk by chunk.
+    pub fn iter_in(
+        &self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> impl Iterator<Item = ([isize; N], &T)> + '_ {
+        self.iter_in_on(None, corner_1, corner_2)
+    }
+}
+
+/// Used to read and write the [CellData] layer of type `T` in a cell map.
+#[derive(SystemParam)]
+pub struct CellDataQueryMut<'w, 's, L, T, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+    T: Send + Sync + 'static,
+{
+    data_q: Query<'w, 's, &'static mut CellData<T>>,
+    map_q: Query<'w, 's, &'static CellMap<L, N>>,
+}
+
+impl<'w, 's, L, T, const N: usize> CellDataQueryMut<'w, 's, L, T, N>
+where
+    L: CellMapLabel + 'static,
+    T: Send + Sync + 'static,
+{
+    /// Gets the value at the given cell.
+    /// # Note
+    /// If no map entity is given, the single map for the label is used.
+    pub fn get_on(&self, map_id: Option<Entity>, cell_c: [isize; N]) -> Option<&T> {
+        let (_, chunk_id, cell_i) = data_index::<L, N>(&self.map_q, map_id, cell_c).ok()?;
+        self.data_q.get(chunk_id).ok()?.get(cell_i)
+    }
+
+    /// Gets the value at the given cell.
+    pub fn get(&self, cell_c: [isize; N]) -> Option<&T> {
+        self.get_on(None, cell_c)
+    }
+
+    /// Gets the value at the given cell mutably.
+    /// # Note
+    /// If no map entity is given, the single map for the label is used.
+    pub fn get_mut_on(&mut self, map_id: Option<Entity>, cell_c: [isize; N]) -> Option<&mut T> {
+        let (_, chunk_id, cell_i) = data_index::<L, N>(&self.map_q, map_id, cell_c).ok()?;
+        self.data_q
+            .get_mut(chunk_id)
+            .ok()?
+            .into_inner()
+            .get_mut(cell_i)
+    }
+
+    /// Gets the value at the given cell mutably.
+    pub fn get_mut(&mut self, cell_c: [isize; N]) -> Option<&mut T> {
+        self.get_mut_on(None, cell_c)
+    }
+
+    /// Sets the value at the given cell, returning the old value.
+    /// # Note
+    /// If no map entity is given, the single map for the label is used.
+    /// This can't add a layer to a chunk, so [CellError::LayerMissing] is returned for chunks without one,
+    /// use [set_cell_data](super::commands::CellCommands::set_cell_data) to set values in them.
+    pub fn set_on(
+        &mut self,
+        map_id: Option<Entity>,
+        cell_c: [isize; N],
+        value: T,
+    ) -> Result<T, CellError<N>> {
+        let (chunk_c, chunk_id, cell_i) = data_index::<L, N>(&self.map_q, map_id, cell_c)?;
+        let mut data = self
+            .data_q
+            .get_mut(chunk_id)
+            .map_err(|_| CellError::LayerMissing { chunk_c })?;
+        let old = data
+            .get_mut(cell_i)
+            .ok_or(CellError::OutOfBounds { cell_c })?;
+        Ok(std::mem::replace(old, value))
+    }
+
+    /// Sets the value at the given cell, returning the old value.
+    pub fn set(&mut self, cell_c: [isize; N], value: T) -> Result<T, CellError<N>> {
+        self.set_on(None, cell_c, value)
+    }
+
+    /// Iterate over the values in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`, skipping cells in chunks without the layer.
+    /// # Note
+    /// If no map entity is given, the single map for the label is used.
+    /// Values are visited chunk by chunk.
+    pub fn iter_in_on(
+        &self,
+        map_id: Option<Entity>,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> impl Iterator<Item = ([isize; N], &T)> + '_ {
+        let map = data_map::<L, N>(&self.map_q, map_id).ok();
+        map.into_iter().flat_map(move |map| {
+            let chunk_size = map.config.chunk_size();
+            chunks_in(map, corner_1, corner_2)
+                .filter_map(|(chunk_id, cells)| Some((self.data_q.get(chunk_id).ok()?, cells)))
+                .flat_map(move |(data, cells)| {
+                    cells.filter_map(move |cell_c| {
+                        Some((cell_c, data.get(calculate_cell_index(cell_c, chunk_size))?))
+                    })
+                })
+        })
+    }
+
+    /// Iterate over the values in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`, skipping cells in chunks without the layer.
+    /// # Note
+    /// Values are visited chunk by chunk.
+    pub fn iter_in(
+        &self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+    ) -> impl Iterator<Item = ([isize; N], &T)> + '_ {
+        self.iter_in_on(None, corner_1, corner_2)
+    }
+
+    /// Calls a function on each value in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`, skipping cells in chunks without the layer.
+    pub fn for_each_in_mut(
+        &mut self,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+        f: impl FnMut([isize; N], &mut T),
+    ) {
+        self.for_each_in_mut_on(None, corner_1, corner_2, f);
+    }
+
+    /// Calls a function on each value in a given space, starting at `corner_1`
+    /// inclusive over `corner_2`, skipping cells in chunks without the layer.
+    /// # Note
+    /// If no map entity is given, the single map for the label is used.
+    pub fn for_each_in_mut_on(
+        &mut self,
+        map_id: Option<Entity>,
+        corner_1: [isize; N],
+        corner_2: [isize; N],
+        mut f: impl FnMut([isize; N], &mut T),
+    ) {
+        let Ok(map) = data_map::<L, N>(&self.map_q, map_id) else {
+            return;
+        };
+        let chunk_size = map.config.chunk_size();
+        for (chunk_id, cells) in chunks_in(map, corner_1, corner_2) {
+            let Ok(mut data) = self.data_q.get_mut(chunk_id) else {
+                continue;
+            };
+            for cell_c in cells {
+                if let Some(value) = data.get_mut(calculate_cell_index(cell_c, chunk_size)) {
+                    f(cell_c, value);
+                }
+            }
+        }
+    }
+}
+
+/// Gets the chunk coordinate and entity, and the index into its layer for a cell.
+#[inline]
+fn data_index<L, const N: usize>(
+    map_q: &Query<&CellMap<L, N>>,
+    map_id: Option<Entity>,
+    cell_c: [isize; N],
+) -> Result<([isize; N], Entity, usize), CellError<N>>
+where
+    L: CellMapLabel + 'static,
+{
+    let map = data_map(map_q, map_id)?;
+    let chunk_size = map.config.chunk_size();
+    let chunk_c = calculate_chunk_coordinate(cell_c, chunk_size);
+    let chunk_id = map
+        .chunks
+        .get(&chunk_c.into())
+        .ok_or(CellError::ChunkMissing { chunk_c })?;
+    Ok((chunk_c, *chunk_id, calculate_cell_index(cell_c, chunk_size)))
+}
+
+/// Gets the given map, or the single map for the label if none is given.
+#[inline]
+fn data_map<'m, L, const N: usize>(
+    map_q: &'m Query<&CellMap<L, N>>,
+    map_id: Option<Entity>,
+) -> Result<&'m CellMap<L, N>, CellError<N>>
+where
+    L: CellMapLabel + 'static,
+{
+    if let Some(map_id) = map_id {
+        map_q.get(map_id).ok()
+    } else {
+        map_q.get_single().ok()
+    }
+    .ok_or(CellError::MapMissing)
+}
+
+/// Walks the chunks of a map overlapping a region once, along with the cells of each chunk inside the region.
+fn chunks_in<L, const N: usize>(
+    map: &CellMap<L, N>,
+    corner_1: [isize; N],
+    corner_2: [isize; N],
+) -> impl Iterator<Item = (Entity, CoordIterator<N>)> + '_
+where
+    L: CellMapLabel + 'static,
+{
+    let (mut corner_1, mut corner_2) = (corner_1, corner_2);
+    for i in 0..N {
+        if corner_1[i] > corner_2[i] {
+            std::mem::swap(&mut corner_1[i], &mut corner_2[i]);
+        }
+    }
+    let chunk_size = map.config.chunk_size();
+    CoordIterator::new(
+        calculate_chunk_coordinate(corner_1, chunk_size),
+        calculate_chunk_coordinate(corner_2, chunk_size),
+    )
+    .filter_map(move |chunk_c| {
+        let chunk_id = map.chunks.get(&chunk_c.into())?;
+        let (mut min, mut max) = calculate_chunk_cell_range(chunk_c, chunk_size);
+        for i in 0..N {
+            min[i] = min[i].max(corner_1[i]);
+            max[i] = max[i].min(corner_2[i]);
+        }
+        (0..N)
+            .all(|i| min[i] <= max[i])
+            .then(|| (*chunk_id, CoordIterator::new(min, max)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::{
+        system::{CommandQueue, Commands, SystemState},
+        world::World,
+    };
+
+    use super::*;
+    use crate::cells::commands::CellCommandExt;
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 4;
+    }
+
+    #[test]
+    fn set_and_get_data() {
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &world)
+            .cells::<TestLayer, 2>()
+            .set_cell_data_batch([([0, 0], 3u8), ([-1, 5], 7u8)]);
+        Commands::new(&mut queue, &world)
+            .cells::<TestLayer, 2>()
+            .spawn_cell([8, 0], ());
+        queue.apply(&mut world);
+
+        let mut state = SystemState::<CellDataQueryMut<TestLayer, u8>>::new(&mut world);
+        let mut data = state.get_mut(&mut world);
+        assert_eq!(data.get([0, 0]), Some(&3));
+        assert_eq!(data.get([1, 0]), Some(&0));
+        assert_eq!(data.set([1, 0], 2), Ok(0));
+        assert_eq!(
+            data.set([40, 0], 1),
+            Err(CellError::ChunkMissing { chunk_c: [10, 0] })
+        );
+        assert_eq!(
+            data.set([8, 0], 1),
+            Err(CellError::LayerMissing { chunk_c: [2, 0] })
+        );
+        data.for_each_in_mut([0, 0], [1, 0], |_, value| *value += 1);
+
+        let mut state = SystemState::<CellDataQuery<TestLayer, u8>>::new(&mut world);
+        let data = state.get(&world);
+        let values = data
+            .iter_in([-1, 0], [1, 0])
+            .map(|(_, value)| *value)
+            .collect::<Vec<_>>();
+        // There is no chunk at [-1, 0], so it is skipped
+        assert_eq!(values, vec![4, 3]);
+        assert_eq!(data.get([-1, 5]), Some(&7));
+    }
+
+    #[test]
+    fn data_on_maps() {
+        let mut world = World::new();
+        let map_1 = world.spawn(CellMap::<TestLayer>::default()).id();
+        let map_2 = world.spawn(CellMap::<TestLayer>::default()).id();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        // Skip every other chunk so the walk has gaps, including negative ones
+        let cells = CoordIterator::new([-9, -9], [8, 8])
+            .filter(|cell_c| calculate_chunk_coordinate(*cell_c, [4, 4])[0] % 2 == 0)
+            .map(|cell_c| (cell_c, (cell_c[0] * 100 + cell_c[1]) as i32))
+            .collect::<Vec<_>>();
+        commands
+            .cells_on::<TestLayer, 2>(map_1)
+            .set_cell_data_batch(cells);
+        commands
+            .cells_on::<TestLayer, 2>(map_2)
+            .set_cell_data([0, 0], 1i32);
+        queue.apply(&mut world);
+
+        let mut state = SystemState::<CellDataQueryMut<TestLayer, i32>>::new(&mut world);
+        let mut data = state.get_mut(&mut world);
+        assert_eq!(data.set_on(Some(map_2), [1, 1], 5), Ok(0));
+        assert_eq!(data.get_on(Some(map_1), [1, 1]), Some(&101));
+        assert_eq!(data.get_on(Some(map_2), [1, 1]), Some(&5));
+        assert_eq!(data.set([1, 1], 5), Err(CellError::MapMissing));
+        // Only chunk [0, 0] was made on map 2
+        assert_eq!(
+            data.set_on(Some(map_2), [4, 0], 5),
+            Err(CellError::ChunkMissing { chunk_c: [1, 0] })
+        );
+
+        let mut visited = Vec::new();
+        data.for_each_in_mut_on(Some(map_2), [-8, -8], [8, 8], |cell_c, value| {
+            *value += 1;
+            visited.push(cell_c);
+        });
+        assert_eq!(visited.len(), 16);
+        assert_eq!(data.get_on(Some(map_2), [1, 1]), Some(&6));
+        assert_eq!(data.get_on(Some(map_1), [1, 1]), Some(&101));
+
+        let mut state = SystemState::<CellDataQuery<TestLayer, i32>>::new(&mut world);
+        let data = state.get(&world);
+        let mut found = data
+            .iter_in_on(Some(map_1), [6, -7], [-6, 5])
+            .collect::<Vec<_>>();
+        found.sort_by_key(|(cell_c, _)| *cell_c);
+        let mut expected = CoordIterator::new([-6, -7], [6, 5])
+            .filter_map(|cell_c| Some((cell_c, data.get_on(Some(map_1), cell_c)?)))
+            .collect::<Vec<_>>();
+        expected.sort_by_key(|(cell_c, _)| *cell_c);
+        assert!(!expected.is_empty());
+        assert_eq!(found, expected);
+        assert_eq!(data.iter_in([6, -7], [-6, 5]).count(), 0);
+    }
+}
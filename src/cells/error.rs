@@ -0,0 +1,43 @@
+use std::fmt::{self, Display};
+
+use bevy::ecs::entity::Entity;
+
+/// Errors that can happen when changing the cells in a map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellError<const N: usize = 2> {
+    /// No map was found for the label, or the given entity isn't a map.
+    MapMissing,
+    /// The map doesn't have a chunk at the coordinate.
+    ChunkMissing { chunk_c: [isize; N] },
+    /// There is no cell at the coordinate.
+    CellEmpty { cell_c: [isize; N] },
+    /// A cell was inserted into a coordinate that already has a cell,
+    /// with [InsertPolicy::Error](super::InsertPolicy::Error).
+    Occupied {
+        cell_c: [isize; N],
+        occupant: Entity,
+    },
+    /// The coordinate is outside the bounds of the map.
+    OutOfBounds { cell_c: [isize; N] },
+    /// The chunk doesn't have the [CellData](super::data::CellData) layer being written to.
+    LayerMissing { chunk_c: [isize; N] },
+}
+
+impl<const N: usize> Display for CellError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellError::MapMissing => write!(f, "cell map not found"),
+            CellError::ChunkMissing { chunk_c } => write!(f, "chunk {chunk_c:?} not found"),
+            CellError::CellEmpty { cell_c } => write!(f, "cell {cell_c:?} is empty"),
+            CellError::Occupied { cell_c, occupant } => {
+                write!(f, "cell {cell_c:?} is already occupied by {occupant:?}")
+            }
+            CellError::OutOfBounds { cell_c } => write!(f, "cell {cell_c:?} is out of bounds"),
+            CellError::LayerMissing { chunk_c } => {
+                write!(f, "chunk {chunk_c:?} doesn't have the data layer")
+            }
+        }
+    }
+}
+
+impl<const N: usize> std::error::Error for CellError<N> {}
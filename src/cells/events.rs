@@ -0,0 +1,245 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    app::{App, Plugin},
+    ecs::{entity::Entity, event::Event},
+};
+
+use super::{error::CellError, CellMapLabel};
+
+/// Registers the cell lifecycle events for a given cell map label.
+/// # Note
+/// Events are only sent for labels that have this plugin (or the events) added,
+/// so maps that don't need them pay nothing.
+pub struct CellEventsPlugin<L, const N: usize = 2>(PhantomData<L>);
+
+impl<L, const N: usize> Default for CellEventsPlugin<L, N> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<L, const N: usize> Plugin for CellEventsPlugin<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.add_event::<CellSpawned<L, N>>()
+            .add_event::<CellDespawned<L, N>>()
+            .add_event::<CellMoved<L, N>>()
+            .add_event::<CellReplaced<L, N>>()
+            .add_event::<CellCommandFailed<L, N>>();
+    }
+}
+
+/// Sent when a cell entity is inserted into a cell map.
+#[derive(Event, Debug)]
+pub struct CellSpawned<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    pub map_id: Entity,
+    pub cell_c: [isize; N],
+    pub entity: Entity,
+    pub label: PhantomData<L>,
+}
+
+/// Sent when a cell entity is removed from a cell map and despawned.
+#[derive(Event, Debug)]
+pub struct CellDespawned<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    pub map_id: Entity,
+    pub cell_c: [isize; N],
+    pub entity: Entity,
+    pub label: PhantomData<L>,
+}
+
+/// Sent when a cell entity is moved from one coordinate to another, including swaps.
+#[derive(Event, Debug)]
+pub struct CellMoved<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    pub map_id: Entity,
+    pub from: [isize; N],
+    pub to: [isize; N],
+    pub entity: Entity,
+    pub label: PhantomData<L>,
+}
+
+/// Sent when a cell is inserted into an occupied coordinate and the old cell is removed from the map.
+/// # Note
+/// The `old` entity no longer exists by the time this event is read,
+/// unless it was returned by [InsertPolicy::ReplaceReturn](super::InsertPolicy::ReplaceReturn).
+#[derive(Event, Debug)]
+pub struct CellReplaced<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    pub map_id: Entity,
+    pub cell_c: [isize; N],
+    pub old: Entity,
+    pub new: Entity,
+    pub label: PhantomData<L>,
+}
+
+/// Sent when a fallible cell command, like [try_move_cell](crate::prelude::CellCommands::try_move_cell), fails.
+#[derive(Event, Debug)]
+pub struct CellCommandFailed<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    /// The map the command was given, or `None` if it used the single map for the label.
+    pub map_id: Option<Entity>,
+    pub error: CellError<N>,
+    pub label: PhantomData<L>,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        app::App,
+        ecs::{
+            event::{Events, ManualEventReader},
+            system::{CommandQueue, Commands},
+            world::World,
+        },
+    };
+
+    use super::*;
+    use crate::{
+        cells::{
+            commands::{insert_cell, insert_cell_with_policy, take_cell, CellCommandExt},
+            InsertPolicy,
+        },
+        CellsPlugin,
+    };
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 16;
+    }
+
+    fn read<E: Event>(world: &World, reader: &mut ManualEventReader<E>) -> usize {
+        reader.read(world.resource::<Events<E>>()).count()
+    }
+
+    #[test]
+    fn insert_and_take_send_events() {
+        let mut app = App::new();
+        app.add_plugins((CellsPlugin, CellEventsPlugin::<TestLayer>::default()));
+        let world = &mut app.world;
+
+        let mut spawned = ManualEventReader::<CellSpawned<TestLayer>>::default();
+        let mut replaced = ManualEventReader::<CellReplaced<TestLayer>>::default();
+
+        let cell_1 = world.spawn_empty().id();
+        let cell_2 = world.spawn_empty().id();
+        insert_cell::<TestLayer, 2>(world, None, [0, 0], cell_1).unwrap();
+        insert_cell::<TestLayer, 2>(world, None, [0, 0], cell_2).unwrap();
+
+        assert_eq!(read(world, &mut spawned), 2);
+        assert_eq!(read(world, &mut replaced), 1);
+
+        // Taking a cell doesn't despawn it, so no event is sent
+        let mut despawned = ManualEventReader::<CellDespawned<TestLayer>>::default();
+        assert_eq!(take_cell::<TestLayer, 2>(world, None, [0, 0]), Some(cell_2));
+        assert_eq!(read(world, &mut despawned), 0);
+    }
+
+    #[test]
+    fn returned_cells_send_replaced_events() {
+        let mut app = App::new();
+        app.add_plugins((CellsPlugin, CellEventsPlugin::<TestLayer>::default()));
+        let world = &mut app.world;
+
+        let cell_1 = world.spawn_empty().id();
+        let cell_2 = world.spawn_empty().id();
+        insert_cell::<TestLayer, 2>(world, None, [0, 0], cell_1).unwrap();
+        insert_cell_with_policy::<TestLayer, 2>(
+            world,
+            None,
+            [0, 0],
+            cell_2,
+            InsertPolicy::ReplaceReturn,
+        )
+        .unwrap();
+
+        let events = ManualEventReader::<CellReplaced<TestLayer>>::default()
+            .read(world.resource::<Events<CellReplaced<TestLayer>>>())
+            .map(|event| (event.cell_c, event.old, event.new))
+            .collect::<Vec<_>>();
+        assert_eq!(events, vec![([0, 0], cell_1, cell_2)]);
+        assert!(world.get_entity(cell_1).is_some());
+    }
+
+    #[test]
+    fn move_sends_moved_event() {
+        let mut app = App::new();
+        app.add_plugins((CellsPlugin, CellEventsPlugin::<TestLayer>::default()));
+        let world = &mut app.world;
+
+        let cell = world.spawn_empty().id();
+        insert_cell::<TestLayer, 2>(world, None, [0, 0], cell).unwrap();
+
+        let mut spawned = ManualEventReader::<CellSpawned<TestLayer>>::default();
+        let mut moved = ManualEventReader::<CellMoved<TestLayer>>::default();
+        read(world, &mut spawned);
+
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, world)
+            .cells::<TestLayer, 2>()
+            .move_cell([0, 0], [20, 0]);
+        queue.apply(world);
+
+        assert_eq!(read(world, &mut spawned), 0);
+        let events = moved
+            .read(world.resource::<Events<CellMoved<TestLayer>>>())
+            .map(|event| (event.from, event.to, event.entity))
+            .collect::<Vec<_>>();
+        assert_eq!(events, vec![([0, 0], [20, 0], cell)]);
+    }
+
+    #[test]
+    fn failed_commands_send_errors() {
+        let mut app = App::new();
+        app.add_plugins((CellsPlugin, CellEventsPlugin::<TestLayer>::default()));
+        let world = &mut app.world;
+
+        let cell_1 = world.spawn_empty().id();
+        let cell_2 = world.spawn_empty().id();
+        insert_cell::<TestLayer, 2>(world, None, [0, 0], cell_1).unwrap();
+        insert_cell::<TestLayer, 2>(world, None, [1, 0], cell_2).unwrap();
+
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, world)
+            .cells::<TestLayer, 2>()
+            .try_move_cell([0, 0], [1, 0])
+            .try_move_cell([2, 0], [3, 0])
+            .try_despawn_cell([40, 0])
+            .try_despawn_chunk([5, 5])
+            .move_cell([2, 0], [3, 0]);
+        queue.apply(world);
+
+        let errors = ManualEventReader::<CellCommandFailed<TestLayer>>::default()
+            .read(world.resource::<Events<CellCommandFailed<TestLayer>>>())
+            .map(|event| event.error)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                CellError::Occupied {
+                    cell_c: [1, 0],
+                    occupant: cell_2
+                },
+                CellError::CellEmpty { cell_c: [2, 0] },
+                CellError::ChunkMissing { chunk_c: [2, 0] },
+                CellError::ChunkMissing { chunk_c: [5, 5] },
+            ]
+        );
+        assert!(world.get_entity(cell_2).is_some());
+    }
+}
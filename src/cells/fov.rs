@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+
+use bevy::ecs::query::{ReadOnlyWorldQuery, WorldQuery};
+
+use super::{cell_query::CellQuery, coords::CoordIterator, CellMapLabel};
+
+/// Octant transforms used to map shadowcasting in the first octant onto the other seven.
+const OCTANTS: [[isize; 4]; 8] = [
+    [1, 0, 0, 1],
+    [0, 1, 1, 0],
+    [0, -1, 1, 0],
+    [-1, 0, 0, 1],
+    [-1, 0, 0, -1],
+    [0, -1, -1, 0],
+    [0, 1, -1, 0],
+    [1, 0, 0, -1],
+];
+
+/// Calculates the cells visible from `origin` within `radius` using recursive shadowcasting,
+/// treating every cell matched by the query as opaque.
+///
+/// Opaque cells that are seen are included in the visible set, which makes it easy
+/// to drive fog of war by updating the cells in the set.
+pub fn field_of_view<L, Q, F>(
+    opaque_q: &CellQuery<L, Q, F, 2>,
+    origin: [isize; 2],
+    radius: usize,
+) -> HashSet<[isize; 2]>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    field_of_view_by(origin, radius, |cell_c| opaque_q.get_at(cell_c).is_some())
+}
+
+/// Calculates the cells visible from `origin` within `radius` using recursive shadowcasting,
+/// using a function over coordinates to check if a cell is opaque.
+///
+/// See [field_of_view] for what is included in the visible set.
+pub fn field_of_view_by(
+    origin: [isize; 2],
+    radius: usize,
+    mut is_opaque: impl FnMut([isize; 2]) -> bool,
+) -> HashSet<[isize; 2]> {
+    let mut visible = HashSet::from([origin]);
+    for octant in OCTANTS {
+        cast_light(
+            origin,
+            radius as isize,
+            1,
+            (1.0, 0.0),
+            octant,
+            &mut visible,
+            &mut is_opaque,
+        );
+    }
+    visible
+}
+
+/// Scans the rows of a single octant, recursing whenever a run of opaque cells splits the light.
+fn cast_light(
+    origin: [isize; 2],
+    radius: isize,
+    row: isize,
+    (mut start, end): (f32, f32),
+    [xx, xy, yx, yy]: [isize; 4],
+    visible: &mut HashSet<[isize; 2]>,
+    is_opaque: &mut impl FnMut([isize; 2]) -> bool,
+) {
+    if start < end {
+        return;
+    }
+
+    let mut next_start = start;
+    for j in row..=radius {
+        let dy = -j;
+        let mut blocked = false;
+        for dx in -j..=0 {
+            let cell_c = [origin[0] + dx * xx + dy * xy, origin[1] + dx * yx + dy * yy];
+            let left_slope = (dx as f32 - 0.5) / (dy as f32 + 0.5);
+            let right_slope = (dx as f32 + 0.5) / (dy as f32 - 0.5);
+            if start < right_slope {
+                continue;
+            } else if end > left_slope {
+                break;
+            }
+
+            if dx * dx + dy * dy <= radius * radius {
+                visible.insert(cell_c);
+            }
+
+            let opaque = is_opaque(cell_c);
+            if blocked {
+                if opaque {
+                    next_start = right_slope;
+                } else {
+                    blocked = false;
+                    start = next_start;
+                }
+            } else if opaque && j < radius {
+                blocked = true;
+                cast_light(
+                    origin,
+                    radius,
+                    j + 1,
+                    (start, left_slope),
+                    [xx, xy, yx, yy],
+                    visible,
+                    is_opaque,
+                );
+                next_start = right_slope;
+            }
+        }
+        if blocked {
+            break;
+        }
+    }
+}
+
+/// Calculates the cells visible from `origin` within `radius` in any number of dimensions,
+/// by casting lines to the edge of the area and treating every cell matched by the query as opaque.
+///
+/// This is intended for 3d voxel maps, see [field_of_view] for what is included in the visible set.
+pub fn voxel_field_of_view<L, Q, F, const N: usize>(
+    opaque_q: &CellQuery<L, Q, F, N>,
+    origin: [isize; N],
+    radius: usize,
+) -> HashSet<[isize; N]>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    voxel_field_of_view_by(origin, radius, |cell_c| opaque_q.get_at(cell_c).is_some())
+}
+
+/// Calculates the cells visible from `origin` within `radius` in any number of dimensions,
+/// using a function over coordinates to check if a cell is opaque.
+///
+/// See [voxel_field_of_view] for how the visible set is found.
+pub fn voxel_field_of_view_by<const N: usize>(
+    origin: [isize; N],
+    radius: usize,
+    mut is_opaque: impl FnMut([isize; N]) -> bool,
+) -> HashSet<[isize; N]> {
+    let radius = radius as isize;
+    let mut visible = HashSet::from([origin]);
+    let shell = CoordIterator::new([-radius; N], [radius; N])
+        .filter(|offset| offset.iter().any(|o| o.abs() == radius));
+    for offset in shell {
+        let mut target = origin;
+        for (c, o) in target.iter_mut().zip(offset) {
+            *c += o;
+        }
+        for cell_c in line(origin, target).skip(1) {
+            if distance_squared(origin, cell_c) > radius * radius {
+                break;
+            }
+            visible.insert(cell_c);
+            if is_opaque(cell_c) {
+                break;
+            }
+        }
+    }
+    visible
+}
+
+/// Returns true if no cell matched by the query lies strictly between `from` and `to`.
+pub fn line_of_sight<L, Q, F, const N: usize>(
+    opaque_q: &CellQuery<L, Q, F, N>,
+    from: [isize; N],
+    to: [isize; N],
+) -> bool
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    line_of_sight_by(from, to, |cell_c| opaque_q.get_at(cell_c).is_some())
+}
+
+/// Returns true if no opaque cell lies strictly between `from` and `to`,
+/// using a function over coordinates to check if a cell is opaque.
+pub fn line_of_sight_by<const N: usize>(
+    from: [isize; N],
+    to: [isize; N],
+    mut is_opaque: impl FnMut([isize; N]) -> bool,
+) -> bool {
+    line(from, to)
+        .filter(|cell_c| *cell_c != from && *cell_c != to)
+        .all(|cell_c| !is_opaque(cell_c))
+}
+
+/// Iterate over the cells on a straight line between two cells, including both ends.
+fn line<const N: usize>(from: [isize; N], to: [isize; N]) -> impl Iterator<Item = [isize; N]> {
+    let steps = from
+        .iter()
+        .zip(to)
+        .map(|(f, t)| f.abs_diff(t))
+        .max()
+        .unwrap_or_default() as isize;
+    (0..=steps).map(move |step| {
+        let mut cell_c = from;
+        if steps > 0 {
+            for (c, t) in cell_c.iter_mut().zip(to) {
+                let delta = (t - *c) as f32 * step as f32 / steps as f32;
+                *c += delta.round() as isize;
+            }
+        }
+        cell_c
+    })
+}
+
+#[inline]
+fn distance_squared<const N: usize>(c1: [isize; N], c2: [isize; N]) -> isize {
+    c1.iter().zip(c2).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn wall(cell_c: [isize; 2]) -> bool {
+        cell_c[0] == 2 && (-1..=1).contains(&cell_c[1])
+    }
+
+    #[test]
+    fn open_field() {
+        let visible = field_of_view_by([0, 0], 2, |_| false);
+        assert_eq!(visible.len(), 13);
+    }
+
+    #[rstest]
+    #[case([2, 0], true)]
+    #[case([3, 0], false)]
+    #[case([4, 1], false)]
+    #[case([0, 4], true)]
+    fn shadowcasting(#[case] cell_c: [isize; 2], #[case] seen: bool) {
+        let visible = field_of_view_by([0, 0], 5, wall);
+        assert_eq!(visible.contains(&cell_c), seen);
+        assert_eq!(line_of_sight_by([0, 0], cell_c, wall), seen);
+    }
+
+    #[test]
+    fn voxel_wall() {
+        let visible = voxel_field_of_view_by([0, 0, 0], 4, |[x, _, _]| x == 2);
+        assert!(visible.contains(&[2, 0, 0]));
+        assert!(!visible.contains(&[3, 0, 0]));
+        assert!(visible.contains(&[-4, 0, 0]));
+        assert!(visible.contains(&[0, 0, 4]));
+    }
+}
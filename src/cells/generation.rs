@@ -0,0 +1,720 @@
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+};
+
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        bundle::Bundle,
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        query::{Added, Without},
+        schedule::IntoSystemConfigs,
+        system::{Command, Commands, Query, Res, ResMut, Resource},
+        world::{EntityWorldMut, Mut, World},
+    },
+    tasks::{AsyncComputeTaskPool, TaskPool},
+};
+
+use super::{
+    commands::{
+        find_map, insert_cell_batch_with_policy, insert_chunk, report_error, spawn_or_find_map,
+        CellCommandExt,
+    },
+    coords::{calculate_chunk_cell_range, CoordIterator},
+    streaming::{stream_chunks, ChunkRequested, ChunkStreaming},
+    CellMap, CellMapLabel, Chunk, ChunkCoord, InsertPolicy,
+};
+
+/// Procedurally fills in chunks of a cell map.
+/// # Note
+/// The generator is a resource, insert it into the world along with a [ChunkGeneratorPlugin]
+/// to generate chunks when they are requested by chunk streaming or first touched, or call
+/// [generate_chunk](super::commands::CellCommands::generate_chunk) directly.
+pub trait ChunkGenerator<L, const N: usize = 2>: Resource
+where
+    L: CellMapLabel + 'static,
+{
+    /// Fills in the chunk at the given chunk coordinate.
+    fn generate(&self, chunk_c: [isize; N], builder: &mut ChunkBuilder<L, N>);
+}
+
+/// Generates chunks for the label with `G` when they are requested by a
+/// [ChunkStreamingPlugin](super::streaming::ChunkStreamingPlugin), or when a chunk is first touched
+/// by spawning a cell or chunk into it.
+/// # Note
+/// Cells spawned by the generator outside of its chunk touch their own chunks, which are generated in turn.
+/// Insert [ChunkGenerated] on a chunk to keep it from being generated.
+pub struct ChunkGeneratorPlugin<L, G, const N: usize = 2>(PhantomData<(L, G)>);
+
+impl<L, G, const N: usize> Default for ChunkGeneratorPlugin<L, G, N> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<L, G, const N: usize> Plugin for ChunkGeneratorPlugin<L, G, N>
+where
+    L: CellMapLabel + 'static,
+    G: ChunkGenerator<L, N>,
+{
+    fn build(&self, app: &mut App) {
+        app.add_event::<ChunkRequested<L, N>>().add_systems(
+            Update,
+            (
+                generate_requested_chunks::<L, G, N>.after(stream_chunks::<L, N>),
+                generate_touched_chunks::<L, G, N>,
+            ),
+        );
+    }
+}
+
+/// Marks a chunk that has been filled in by a generator, so it isn't generated again.
+/// # Note
+/// Chunks created by spawning cells or chunks don't have this until they are generated,
+/// chunks loaded from a save are treated as generated.
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct ChunkGenerated;
+
+/// Collects the contents of a chunk while it's generated so cells are inserted in a single batch.
+pub struct ChunkBuilder<'w, L, const N: usize = 2> {
+    world: &'w mut World,
+    chunk_c: [isize; N],
+    chunk_id: Entity,
+    chunk_size: [usize; N],
+    cells: Vec<([isize; N], Entity)>,
+    label: PhantomData<L>,
+}
+
+impl<'w, L, const N: usize> ChunkBuilder<'w, L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    /// Gets the coordinate of the chunk being generated.
+    pub fn chunk_c(&self) -> [isize; N] {
+        self.chunk_c
+    }
+
+    /// Gets the chunk entity, to insert components onto the chunk.
+    pub fn chunk(&mut self) -> EntityWorldMut<'_> {
+        self.world.entity_mut(self.chunk_id)
+    }
+
+    /// Iterates over the cell coordinates inside the chunk.
+    pub fn cell_coords(&self) -> CoordIterator<N> {
+        let (corner_1, corner_2) = calculate_chunk_cell_range(self.chunk_c, self.chunk_size);
+        CoordIterator::new(corner_1, corner_2)
+    }
+
+    /// Spawns a cell in the chunk and returns the cell entity.
+    /// # Note
+    /// Cells are inserted into the map once generation finishes, cells outside of the chunk
+    /// are inserted into their own chunks.
+    pub fn spawn_cell<B: Bundle>(&mut self, cell_c: [isize; N], bundle: B) -> Entity {
+        let cell_id = self.world.spawn(bundle).id();
+        self.cells.push((cell_c, cell_id));
+        cell_id
+    }
+}
+
+/// Runs the generator for a chunk if it hasn't been generated yet.
+pub(crate) struct GenerateChunk<L, G, const N: usize = 2> {
+    pub map_id: Option<Entity>,
+    pub chunk_c: [isize; N],
+    pub label: PhantomData<(L, G)>,
+}
+
+impl<L, G, const N: usize> Command for GenerateChunk<L, G, N>
+where
+    L: CellMapLabel + Send + 'static,
+    G: ChunkGenerator<L, N>,
+{
+    fn apply(self, world: &mut World) {
+        generate_chunk::<L, G, N>(world, self.map_id, self.chunk_c);
+    }
+}
+
+/// Generates the chunk with `G` and inserts it and its cells into the map.
+/// If the chunk was already touched by other cells, it's filled in around them, since generated cells
+/// never replace cells in the map.
+/// Does nothing if the chunk has been generated or the generator resource is missing,
+/// and reports [MapMissing](super::error::CellError::MapMissing) if the given map entity doesn't exist.
+pub fn generate_chunk<L, G, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    chunk_c: [isize; N],
+) where
+    L: CellMapLabel + Send + 'static,
+    G: ChunkGenerator<L, N>,
+{
+    if !world.contains_resource::<G>() {
+        return;
+    }
+    let map_id = match spawn_or_find_map::<L, N>(world, map_id) {
+        Ok(map_id) => map_id,
+        Err(error) => {
+            report_error::<L, N>(world, map_id, error);
+            return;
+        }
+    };
+    let Some(chunk_id) = claim_chunk::<L, N>(world, map_id, chunk_c) else {
+        return;
+    };
+    let chunk_size = world
+        .get::<CellMap<L, N>>(map_id)
+        .map_or_else(L::chunk_size, |map| map.config.chunk_size());
+
+    let cells = world.resource_scope(|world, generator: Mut<G>| {
+        let mut builder = ChunkBuilder {
+            world,
+            chunk_c,
+            chunk_id,
+            chunk_size,
+            cells: Vec::new(),
+            label: PhantomData,
+        };
+        generator.generate(chunk_c, &mut builder);
+        builder.cells
+    });
+
+    insert_cell_batch_with_policy::<L, N>(world, Some(map_id), cells, InsertPolicy::Reject);
+}
+
+/// Gets the chunk to generate into and marks it as generated, spawning it if the map doesn't have it.
+/// Returns `None` if the chunk has already been generated.
+fn claim_chunk<L, const N: usize>(
+    world: &mut World,
+    map_id: Entity,
+    chunk_c: [isize; N],
+) -> Option<Entity>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let existing = world
+        .get::<CellMap<L, N>>(map_id)?
+        .chunks
+        .get(&chunk_c.into())
+        .copied()
+        .filter(|chunk_id| world.get::<Chunk>(*chunk_id).is_some());
+    match existing {
+        Some(chunk_id) if world.get::<ChunkGenerated>(chunk_id).is_some() => None,
+        Some(chunk_id) => {
+            world.entity_mut(chunk_id).insert(ChunkGenerated);
+            Some(chunk_id)
+        }
+        None => {
+            let chunk_id = world.spawn(ChunkGenerated).id();
+            // The map exists, so this can't fail
+            let _ = insert_chunk::<L, N>(world, Some(map_id), chunk_c, chunk_id);
+            Some(chunk_id)
+        }
+    }
+}
+
+fn generate_touched_chunks<L, G, const N: usize>(
+    mut commands: Commands,
+    touched: Query<(Entity, &ChunkCoord<N>), (Added<Chunk>, Without<ChunkGenerated>)>,
+    maps: Query<(Entity, &CellMap<L, N>)>,
+) where
+    L: CellMapLabel + 'static,
+    G: ChunkGenerator<L, N>,
+{
+    for (chunk_id, chunk_c) in touched.iter() {
+        // Chunks of other labels aren't in any of the maps
+        let Some((map_id, _)) = maps
+            .iter()
+            .find(|(_, map)| map.chunks.get(&(**chunk_c).into()) == Some(&chunk_id))
+        else {
+            continue;
+        };
+        commands
+            .cells_on::<L, N>(map_id)
+            .generate_chunk::<G>(**chunk_c);
+    }
+}
+
+fn generate_requested_chunks<L, G, const N: usize>(
+    mut commands: Commands,
+    mut requests: EventReader<ChunkRequested<L, N>>,
+) where
+    L: CellMapLabel + 'static,
+    G: ChunkGenerator<L, N>,
+{
+    for request in requests.read() {
+        match request.map_id {
+            Some(map_id) => commands.cells_on::<L, N>(map_id),
+            None => commands.cells::<L, N>(),
+        }
+        .generate_chunk::<G>(request.chunk_c);
+    }
+}
+
+// ================
+// Async Generation
+// ================
+
+/// Procedurally describes chunks of a cell map off the main thread.
+/// # Note
+/// The generator is a resource that is cloned into each generation task, so keep it cheap to clone.
+/// Add an [AsyncChunkGeneratorPlugin] to generate chunks requested by chunk streaming.
+pub trait AsyncChunkGenerator<L, const N: usize = 2>: Resource + Clone
+where
+    L: CellMapLabel + 'static,
+{
+    /// The bundle spawned for each generated cell.
+    type Bundle: Bundle;
+
+    /// Describes the cells of the chunk at the given chunk coordinate.
+    fn generate(&self, chunk_c: [isize; N]) -> GeneratedChunk<Self::Bundle, N>;
+}
+
+/// A plain data description of a generated chunk's cells.
+pub struct GeneratedChunk<B, const N: usize = 2> {
+    pub cells: Vec<([isize; N], B)>,
+}
+
+impl<B, const N: usize> Default for GeneratedChunk<B, N> {
+    fn default() -> Self {
+        Self { cells: Vec::new() }
+    }
+}
+
+impl<B, const N: usize> GeneratedChunk<B, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a cell to be spawned with the given bundle.
+    pub fn spawn_cell(&mut self, cell_c: [isize; N], bundle: B) {
+        self.cells.push((cell_c, bundle));
+    }
+}
+
+/// Runs an [AsyncChunkGenerator] on the [AsyncComputeTaskPool] when chunks are requested by a
+/// [ChunkStreamingPlugin](super::streaming::ChunkStreamingPlugin), and inserts the results.
+/// # Note
+/// Tasks are cancelled when chunk streaming no longer wants their chunk.
+/// Unlike the [ChunkGeneratorPlugin], touching a chunk doesn't start a task, but a chunk touched
+/// while its task runs is still filled in around the cells already there.
+pub struct AsyncChunkGeneratorPlugin<L, G, const N: usize = 2>(PhantomData<(L, G)>);
+
+impl<L, G, const N: usize> Default for AsyncChunkGeneratorPlugin<L, G, N> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<L, G, const N: usize> Plugin for AsyncChunkGeneratorPlugin<L, G, N>
+where
+    L: CellMapLabel + 'static,
+    G: AsyncChunkGenerator<L, N>,
+{
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ChunkGenerationTasks::<L, G, N>::default())
+            .add_event::<ChunkRequested<L, N>>()
+            .add_systems(
+                Update,
+                (
+                    start_generation_tasks::<L, G, N>,
+                    cancel_generation_tasks::<L, G, N>,
+                    apply_generated_chunks::<L, G, N>,
+                )
+                    .chain()
+                    .after(stream_chunks::<L, N>),
+            );
+    }
+}
+
+/// The map a chunk is generated for, `None` for the single map of the label, and the chunk coordinate.
+type TaskKey<const N: usize> = (Option<Entity>, [isize; N]);
+
+type GenerationResult<B, const N: usize> = (TaskKey<N>, Arc<AtomicBool>, GeneratedChunk<B, N>);
+
+/// Tracks the running generation tasks for a label.
+#[derive(Resource)]
+pub struct ChunkGenerationTasks<L, G, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+    G: AsyncChunkGenerator<L, N>,
+{
+    running: HashMap<TaskKey<N>, Arc<AtomicBool>>,
+    sender: Sender<GenerationResult<G::Bundle, N>>,
+    receiver: Mutex<Receiver<GenerationResult<G::Bundle, N>>>,
+    label: PhantomData<L>,
+}
+
+impl<L, G, const N: usize> Default for ChunkGenerationTasks<L, G, N>
+where
+    L: CellMapLabel + 'static,
+    G: AsyncChunkGenerator<L, N>,
+{
+    fn default() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            running: HashMap::new(),
+            sender,
+            receiver: Mutex::new(receiver),
+            label: PhantomData,
+        }
+    }
+}
+
+impl<L, G, const N: usize> ChunkGenerationTasks<L, G, N>
+where
+    L: CellMapLabel + 'static,
+    G: AsyncChunkGenerator<L, N>,
+{
+    /// Returns true if the chunk is being generated for the single map of the label.
+    pub fn is_running(&self, chunk_c: [isize; N]) -> bool {
+        self.is_running_on(None, chunk_c)
+    }
+
+    /// Returns true if the chunk is being generated for the map.
+    /// If no map entity is given, the single map for the label is used.
+    pub fn is_running_on(&self, map_id: Option<Entity>, chunk_c: [isize; N]) -> bool {
+        self.running.contains_key(&(map_id, chunk_c))
+    }
+
+    /// Starts generating a chunk for the single map of the label, unless it's already being generated.
+    pub fn spawn(&mut self, chunk_c: [isize; N], generator: G) {
+        self.spawn_on(None, chunk_c, generator);
+    }
+
+    /// Starts generating a chunk for the map, unless it's already being generated.
+    /// If no map entity is given, the single map for the label is used, and spawned if it doesn't exist.
+    pub fn spawn_on(&mut self, map_id: Option<Entity>, chunk_c: [isize; N], generator: G) {
+        let key = (map_id, chunk_c);
+        if self.running.contains_key(&key) {
+            return;
+        }
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.running.insert(key, cancelled.clone());
+
+        let sender = self.sender.clone();
+        AsyncComputeTaskPool::get_or_init(TaskPool::default)
+            .spawn(async move {
+                if cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+                let chunk = generator.generate(chunk_c);
+                // The receiver only goes away with the app
+                let _ = sender.send((key, cancelled, chunk));
+            })
+            .detach();
+    }
+
+    /// Cancels generating a chunk for the single map of the label, returns false if it wasn't being generated.
+    /// # Note
+    /// A task that has already started runs to completion, but its result is thrown away.
+    pub fn cancel(&mut self, chunk_c: [isize; N]) -> bool {
+        self.cancel_on(None, chunk_c)
+    }
+
+    /// Cancels generating a chunk for the map, returns false if it wasn't being generated.
+    /// If no map entity is given, the single map for the label is used.
+    pub fn cancel_on(&mut self, map_id: Option<Entity>, chunk_c: [isize; N]) -> bool {
+        if let Some(cancelled) = self.running.remove(&(map_id, chunk_c)) {
+            cancelled.store(true, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Takes the results of finished tasks that haven't been cancelled.
+    fn take_finished(&mut self) -> Vec<(TaskKey<N>, GeneratedChunk<G::Bundle, N>)> {
+        let receiver = self.receiver.get_mut().unwrap();
+        let mut finished = Vec::new();
+        while let Ok((key, cancelled, chunk)) = receiver.try_recv() {
+            if cancelled.load(Ordering::Relaxed) {
+                continue;
+            }
+            if self
+                .running
+                .get(&key)
+                .is_some_and(|running| Arc::ptr_eq(running, &cancelled))
+            {
+                self.running.remove(&key);
+            }
+            finished.push((key, chunk));
+        }
+        finished
+    }
+}
+
+fn start_generation_tasks<L, G, const N: usize>(
+    mut requests: EventReader<ChunkRequested<L, N>>,
+    mut tasks: ResMut<ChunkGenerationTasks<L, G, N>>,
+    generator: Option<Res<G>>,
+) where
+    L: CellMapLabel + 'static,
+    G: AsyncChunkGenerator<L, N>,
+{
+    let Some(generator) = generator else {
+        return;
+    };
+    for request in requests.read() {
+        tasks.spawn_on(request.map_id, request.chunk_c, generator.clone());
+    }
+}
+
+fn cancel_generation_tasks<L, G, const N: usize>(
+    mut tasks: ResMut<ChunkGenerationTasks<L, G, N>>,
+    streaming: Option<Res<ChunkStreaming<L, N>>>,
+) where
+    L: CellMapLabel + 'static,
+    G: AsyncChunkGenerator<L, N>,
+{
+    let Some(streaming) = streaming else {
+        return;
+    };
+    let unwanted = tasks
+        .running
+        .keys()
+        .filter(|(map_id, chunk_c)| !streaming.is_requested_on(*map_id, *chunk_c))
+        .copied()
+        .collect::<Vec<_>>();
+    for (map_id, chunk_c) in unwanted {
+        tasks.cancel_on(map_id, chunk_c);
+    }
+}
+
+fn apply_generated_chunks<L, G, const N: usize>(world: &mut World)
+where
+    L: CellMapLabel + Send + 'static,
+    G: AsyncChunkGenerator<L, N>,
+{
+    let finished = world
+        .resource_mut::<ChunkGenerationTasks<L, G, N>>()
+        .take_finished();
+    for ((map_id, chunk_c), chunk) in finished {
+        // Chunks generated for a map that has since been despawned are thrown away
+        if map_id.is_some() && find_map::<L, N>(world, map_id).is_none() {
+            continue;
+        }
+        let Ok(map_id) = spawn_or_find_map::<L, N>(world, map_id) else {
+            continue;
+        };
+        if claim_chunk::<L, N>(world, map_id, chunk_c).is_none() {
+            continue;
+        }
+
+        let cells = chunk
+            .cells
+            .into_iter()
+            .map(|(cell_c, bundle)| (cell_c, world.spawn(bundle).id()))
+            .collect::<Vec<_>>();
+        insert_cell_batch_with_policy::<L, N>(world, Some(map_id), cells, InsertPolicy::Reject);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::{component::Component, query::With};
+
+    use super::*;
+    use crate::{
+        cells::{
+            commands::CellWorldExt, coords::calculate_chunk_coordinate, streaming::ChunkAnchor,
+            streaming::ChunkStreamingPlugin, CellCoord, Chunk,
+        },
+        CellsPlugin,
+    };
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 4;
+    }
+
+    #[derive(Component)]
+    struct Floor;
+
+    #[derive(Resource)]
+    struct Checkerboard;
+
+    impl ChunkGenerator<TestLayer> for Checkerboard {
+        fn generate(&self, _: [isize; 2], builder: &mut ChunkBuilder<TestLayer>) {
+            let cell_cs = builder
+                .cell_coords()
+                .filter(|[x, y]| (x + y) % 2 == 0)
+                .collect::<Vec<_>>();
+            for cell_c in cell_cs {
+                builder.spawn_cell(cell_c, Floor);
+            }
+        }
+    }
+
+    #[test]
+    fn generates_streamed_chunks() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellsPlugin,
+            ChunkStreamingPlugin::<TestLayer>::default(),
+            ChunkGeneratorPlugin::<TestLayer, Checkerboard>::default(),
+        ))
+        .insert_resource(Checkerboard)
+        .world
+        .spawn(ChunkAnchor::<TestLayer>::new([0, 0], 0));
+
+        app.update();
+
+        let world = &mut app.world;
+        assert_eq!(world.query::<&Chunk>().iter(world).count(), 1);
+        let mut cells = world
+            .query::<(&CellCoord<2>, &Floor)>()
+            .iter(world)
+            .map(|(cell_c, _)| **cell_c)
+            .collect::<Vec<_>>();
+        cells.sort();
+        assert_eq!(cells.len(), 8);
+        assert_eq!(cells[0], [0, 0]);
+
+        // Generating an existing chunk does nothing
+        generate_chunk::<TestLayer, Checkerboard, 2>(world, None, [0, 0]);
+        assert_eq!(world.query::<&Floor>().iter(world).count(), 8);
+    }
+
+    #[derive(Component)]
+    struct Wall;
+
+    #[test]
+    fn generates_touched_chunks() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellsPlugin,
+            ChunkGeneratorPlugin::<TestLayer, Checkerboard>::default(),
+        ))
+        .insert_resource(Checkerboard);
+
+        // Touching the chunk with a cell doesn't count as generating it
+        let wall = app
+            .world
+            .cells::<TestLayer, 2>()
+            .spawn_cell([0, 0], Wall)
+            .unwrap();
+        app.update();
+
+        let world = &mut app.world;
+        assert_eq!(world.query::<&Chunk>().iter(world).count(), 1);
+        assert_eq!(world.query::<&Floor>().iter(world).count(), 7);
+        assert_eq!(world.cells::<TestLayer, 2>().get_at([0, 0]), Some(wall));
+
+        generate_chunk::<TestLayer, Checkerboard, 2>(world, None, [0, 0]);
+        app.update();
+        assert_eq!(app.world.query::<&Floor>().iter(&app.world).count(), 7);
+    }
+
+    #[test]
+    fn generates_negative_chunks() {
+        let mut world = World::new();
+        world.insert_resource(Checkerboard);
+        generate_chunk::<TestLayer, Checkerboard, 2>(&mut world, None, [-1, -2]);
+
+        // Every generated cell lands in the chunk, so no neighbouring chunk is spawned
+        assert_eq!(world.query::<&Chunk>().iter(&world).count(), 1);
+        let cell_cs = world
+            .query_filtered::<&CellCoord<2>, With<Floor>>()
+            .iter(&world)
+            .map(|cell_c| **cell_c)
+            .collect::<Vec<_>>();
+        assert_eq!(cell_cs.len(), 6);
+        assert!(cell_cs
+            .iter()
+            .all(|cell_c| calculate_chunk_coordinate(*cell_c, [4, 4]) == [-1, -2]));
+    }
+
+    #[derive(Resource, Clone)]
+    struct AsyncFloor;
+
+    impl AsyncChunkGenerator<TestLayer> for AsyncFloor {
+        type Bundle = Floor;
+
+        fn generate(&self, chunk_c: [isize; 2]) -> GeneratedChunk<Floor> {
+            let mut chunk = GeneratedChunk::new();
+            chunk.spawn_cell(chunk_c.map(|c| c * TestLayer::CHUNK_SIZE as isize), Floor);
+            chunk
+        }
+    }
+
+    #[test]
+    fn async_generation_and_cancellation() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellsPlugin,
+            AsyncChunkGeneratorPlugin::<TestLayer, AsyncFloor>::default(),
+        ))
+        .insert_resource(AsyncFloor);
+
+        let mut tasks = app
+            .world
+            .resource_mut::<ChunkGenerationTasks<TestLayer, AsyncFloor>>();
+        tasks.spawn([0, 0], AsyncFloor);
+        tasks.spawn([1, 0], AsyncFloor);
+        assert!(tasks.cancel([1, 0]));
+
+        for _ in 0..1000 {
+            app.update();
+            if !app
+                .world
+                .resource::<ChunkGenerationTasks<TestLayer, AsyncFloor>>()
+                .is_running([0, 0])
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        let world = &mut app.world;
+        let cells = world
+            .query::<(&CellCoord<2>, &Floor)>()
+            .iter(world)
+            .map(|(cell_c, _)| **cell_c)
+            .collect::<Vec<_>>();
+        assert_eq!(cells, vec![[0, 0]]);
+    }
+
+    #[test]
+    fn async_generation_on_a_map() {
+        let mut app = App::new();
+        app.add_plugins((
+            CellsPlugin,
+            AsyncChunkGeneratorPlugin::<TestLayer, AsyncFloor>::default(),
+        ))
+        .insert_resource(AsyncFloor);
+        let map_1 = app.world.spawn(CellMap::<TestLayer>::default()).id();
+        let map_2 = app.world.spawn(CellMap::<TestLayer>::default()).id();
+
+        app.world
+            .resource_mut::<ChunkGenerationTasks<TestLayer, AsyncFloor>>()
+            .spawn_on(Some(map_2), [1, 0], AsyncFloor);
+        for _ in 0..1000 {
+            app.update();
+            if !app
+                .world
+                .resource::<ChunkGenerationTasks<TestLayer, AsyncFloor>>()
+                .is_running_on(Some(map_2), [1, 0])
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        let chunks = |map_id| {
+            app.world
+                .get::<CellMap<TestLayer>>(map_id)
+                .unwrap()
+                .chunks
+                .len()
+        };
+        assert_eq!(chunks(map_1), 0);
+        assert_eq!(chunks(map_2), 1);
+    }
+}
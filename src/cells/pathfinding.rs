@@ -0,0 +1,425 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+};
+
+use bevy::ecs::query::{ReadOnlyWorldQuery, WorldQuery};
+
+use super::{
+    cell_query::CellQuery,
+    coords::{neighbors, Adjacency},
+    CellMapLabel,
+};
+
+/// How paths are allowed to move along more than one axis at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiagonalPolicy {
+    /// Diagonal moves are allowed whenever the target cell is passable.
+    Always,
+    /// Diagonal moves are only allowed if every axis aligned step they skip over is passable,
+    /// so paths can't squeeze between two blocked cells.
+    NoCornerCutting,
+    /// Only moves along a single axis are allowed, regardless of the adjacency.
+    Never,
+}
+
+/// Settings used to control a path search.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PathSettings {
+    /// Which cells can be moved to from a given cell.
+    pub adjacency: Adjacency,
+    /// How moves along more than one axis are treated.
+    pub diagonals: DiagonalPolicy,
+    /// The maximum number of cells to expand before giving up on the search.
+    /// # Note
+    /// Without a limit, searching an unbounded map for a goal that can't be reached never ends.
+    pub max_visited: Option<usize>,
+}
+
+impl Default for PathSettings {
+    fn default() -> Self {
+        Self {
+            adjacency: Adjacency::Corner,
+            diagonals: DiagonalPolicy::NoCornerCutting,
+            max_visited: Some(Self::DEFAULT_MAX_VISITED),
+        }
+    }
+}
+
+impl PathSettings {
+    /// The number of cells expanded by default before a search gives up.
+    pub const DEFAULT_MAX_VISITED: usize = 65536;
+
+    #[inline]
+    fn max_axes<const N: usize>(&self) -> usize {
+        match self.diagonals {
+            DiagonalPolicy::Never => 1,
+            _ => self.adjacency.max_axes::<N>(),
+        }
+    }
+}
+
+/// Finds the cheapest path between two cells using A*.
+///
+/// `cost_f` is given the coordinate of a cell and the query item for that cell (if there is one),
+/// and returns the cost of moving into that cell, or `None` if the cell can't be entered.
+/// Costs are treated as at least 1 so the heuristic never overestimates.
+/// The returned path includes both `start` and `goal`.
+pub fn astar<'a, 'w, 's, L, Q, F, C, const N: usize>(
+    cell_q: &'a CellQuery<'w, 's, L, Q, F, N>,
+    start: [isize; N],
+    goal: [isize; N],
+    settings: &PathSettings,
+    mut cost_f: C,
+) -> Option<Vec<[isize; N]>>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+    C: FnMut(
+        [isize; N],
+        Option<<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'a>>,
+    ) -> Option<u32>,
+{
+    astar_by(start, goal, settings, |cell_c| {
+        cost_f(cell_c, cell_q.get_at(cell_c))
+    })
+}
+
+/// Finds the cheapest path between two cells using Dijkstra's algorithm.
+///
+/// See [astar] for how `cost_f` is used.
+pub fn dijkstra<'a, 'w, 's, L, Q, F, C, const N: usize>(
+    cell_q: &'a CellQuery<'w, 's, L, Q, F, N>,
+    start: [isize; N],
+    goal: [isize; N],
+    settings: &PathSettings,
+    mut cost_f: C,
+) -> Option<Vec<[isize; N]>>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+    C: FnMut(
+        [isize; N],
+        Option<<<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'a>>,
+    ) -> Option<u32>,
+{
+    dijkstra_by(start, goal, settings, |cell_c| {
+        cost_f(cell_c, cell_q.get_at(cell_c))
+    })
+}
+
+/// Finds the shortest path between two cells using A*, treating every cell
+/// matched by the query as blocked and every other cell as open.
+/// Cells outside the bounds of the map are blocked too.
+pub fn astar_blocked<L, Q, F, const N: usize>(
+    blocked_q: &CellQuery<L, Q, F, N>,
+    start: [isize; N],
+    goal: [isize; N],
+    settings: &PathSettings,
+) -> Option<Vec<[isize; N]>>
+where
+    L: CellMapLabel + 'static,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    astar(blocked_q, start, goal, settings, |cell_c, blocked| {
+        (blocked.is_none() && blocked_q.contains(cell_c)).then_some(1)
+    })
+}
+
+/// Finds the cheapest path between two cells using A*, using a cost function over coordinates.
+/// This is useful for searching maps that aren't resolved through a [CellQuery] directly.
+///
+/// See [astar] for how `cost_f` is used.
+pub fn astar_by<const N: usize>(
+    start: [isize; N],
+    goal: [isize; N],
+    settings: &PathSettings,
+    cost_f: impl FnMut([isize; N]) -> Option<u32>,
+) -> Option<Vec<[isize; N]>> {
+    let max_axes = settings.max_axes::<N>();
+    search(start, goal, settings, cost_f, |cell_c| {
+        heuristic(cell_c, goal, max_axes)
+    })
+}
+
+/// Finds the cheapest path between two cells using Dijkstra's algorithm,
+/// using a cost function over coordinates.
+///
+/// See [astar] for how `cost_f` is used.
+pub fn dijkstra_by<const N: usize>(
+    start: [isize; N],
+    goal: [isize; N],
+    settings: &PathSettings,
+    cost_f: impl FnMut([isize; N]) -> Option<u32>,
+) -> Option<Vec<[isize; N]>> {
+    search(start, goal, settings, cost_f, |_| 0)
+}
+
+/// The minimum number of steps between two cells when moving along at most `max_axes` at once.
+#[inline]
+fn heuristic<const N: usize>(cell_c: [isize; N], goal: [isize; N], max_axes: usize) -> u64 {
+    let mut manhattan = 0;
+    let mut chebyshev = 0;
+    for (c, g) in cell_c.iter().zip(goal) {
+        let d = c.abs_diff(g) as u64;
+        manhattan += d;
+        chebyshev = chebyshev.max(d);
+    }
+    chebyshev.max(manhattan.div_ceil(max_axes.max(1) as u64))
+}
+
+#[derive(PartialEq, Eq)]
+struct Node<const N: usize> {
+    estimate: u64,
+    cost: u64,
+    cell_c: [isize; N],
+}
+
+impl<const N: usize> Ord for Node<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Prefer nodes closer to the goal when estimates tie
+        (self.estimate, Reverse(self.cost), self.cell_c).cmp(&(
+            other.estimate,
+            Reverse(other.cost),
+            other.cell_c,
+        ))
+    }
+}
+
+impl<const N: usize> PartialOrd for Node<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn search<const N: usize>(
+    start: [isize; N],
+    goal: [isize; N],
+    settings: &PathSettings,
+    mut cost_f: impl FnMut([isize; N]) -> Option<u32>,
+    heuristic_f: impl Fn([isize; N]) -> u64,
+) -> Option<Vec<[isize; N]>> {
+    let max_axes = settings.max_axes::<N>();
+    let adjacency = Adjacency::Axes(max_axes);
+
+    // Cache costs, since corner cutting checks look at the same cells many times
+    let mut costs = HashMap::<[isize; N], Option<u32>>::new();
+    let mut cost_at = |cell_c: [isize; N]| -> Option<u32> {
+        *costs
+            .entry(cell_c)
+            .or_insert_with(|| cost_f(cell_c).map(|cost| cost.max(1)))
+    };
+
+    // Nothing to search for if the goal can't be entered
+    cost_at(goal)?;
+
+    let mut visited = HashMap::<[isize; N], (u64, Option<[isize; N]>)>::new();
+    let mut open = BinaryHeap::new();
+
+    visited.insert(start, (0, None));
+    open.push(Reverse(Node {
+        estimate: heuristic_f(start),
+        cost: 0,
+        cell_c: start,
+    }));
+
+    let mut expanded = 0;
+    while let Some(Reverse(node)) = open.pop() {
+        if node.cell_c == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some((_, Some(parent))) = visited.get(&current) {
+                path.push(*parent);
+                current = *parent;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        // Skip stale entries
+        if visited
+            .get(&node.cell_c)
+            .is_some_and(|(cost, _)| *cost < node.cost)
+        {
+            continue;
+        }
+
+        expanded += 1;
+        if settings.max_visited.is_some_and(|max| expanded > max) {
+            return None;
+        }
+
+        for neighbor_c in neighbors(node.cell_c, adjacency) {
+            let step_cost = if let Some(step_cost) = cost_at(neighbor_c) {
+                step_cost as u64
+            } else {
+                continue;
+            };
+
+            if settings.diagonals == DiagonalPolicy::NoCornerCutting
+                && !corner_is_clear(node.cell_c, neighbor_c, &mut cost_at)
+            {
+                continue;
+            }
+
+            let cost = node.cost + step_cost;
+            match visited.entry(neighbor_c) {
+                Entry::Occupied(o) if o.get().0 <= cost => continue,
+                Entry::Occupied(mut o) => {
+                    o.insert((cost, Some(node.cell_c)));
+                }
+                Entry::Vacant(v) => {
+                    v.insert((cost, Some(node.cell_c)));
+                }
+            }
+
+            open.push(Reverse(Node {
+                estimate: cost + heuristic_f(neighbor_c),
+                cost,
+                cell_c: neighbor_c,
+            }));
+        }
+    }
+
+    None
+}
+
+/// Checks that every axis aligned step skipped by a diagonal move is passable.
+#[inline]
+fn corner_is_clear<const N: usize>(
+    from: [isize; N],
+    to: [isize; N],
+    cost_at: &mut impl FnMut([isize; N]) -> Option<u32>,
+) -> bool {
+    let axes = from.iter().zip(to).filter(|(f, t)| **f != *t).count();
+    if axes < 2 {
+        return true;
+    }
+
+    (0..N).filter(|i| from[*i] != to[*i]).all(|i| {
+        let mut step_c = from;
+        step_c[i] = to[i];
+        cost_at(step_c).is_some()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::{system::SystemState, world::World};
+
+    use super::*;
+    use crate::cells::{commands::CellWorldExt, CellMap, CellMapConfig};
+
+    fn open_except(blocked: &'static [[isize; 2]]) -> impl FnMut([isize; 2]) -> Option<u32> {
+        move |cell_c| (!blocked.contains(&cell_c)).then_some(1)
+    }
+
+    #[test]
+    fn straight_path() {
+        let settings = PathSettings {
+            diagonals: DiagonalPolicy::Never,
+            ..Default::default()
+        };
+        let path = astar_by([0, 0], [3, 0], &settings, open_except(&[])).unwrap();
+        assert_eq!(path, vec![[0, 0], [1, 0], [2, 0], [3, 0]]);
+    }
+
+    #[test]
+    fn path_around_wall() {
+        let settings = PathSettings {
+            adjacency: Adjacency::Face,
+            ..Default::default()
+        };
+        let wall = &[[1, -1], [1, 0], [1, 1]];
+        let astar_path = astar_by([0, 0], [2, 0], &settings, open_except(wall)).unwrap();
+        let dijkstra_path = dijkstra_by([0, 0], [2, 0], &settings, open_except(wall)).unwrap();
+        assert_eq!(astar_path.len(), 7);
+        assert_eq!(astar_path.len(), dijkstra_path.len());
+        assert!(astar_path.iter().all(|cell_c| !wall.contains(cell_c)));
+    }
+
+    #[test]
+    fn no_corner_cutting() {
+        let blocked = &[[1, 0], [0, 1]];
+        let mut settings = PathSettings {
+            max_visited: Some(64),
+            ..Default::default()
+        };
+        // The only way through is diagonally between the two blocked cells
+        let enclosed = |cell_c: [isize; 2]| {
+            (!blocked.contains(&cell_c) && cell_c.iter().all(|c| (0..=1).contains(c))).then_some(1)
+        };
+        assert_eq!(astar_by([0, 0], [1, 1], &settings, enclosed), None);
+
+        settings.diagonals = DiagonalPolicy::Always;
+        assert_eq!(
+            astar_by([0, 0], [1, 1], &settings, enclosed),
+            Some(vec![[0, 0], [1, 1]])
+        );
+    }
+
+    #[test]
+    fn budget_exhausted() {
+        let settings = PathSettings {
+            max_visited: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(astar_by([0, 0], [100, 100], &settings, |_| None), None);
+        assert_eq!(
+            astar_by([0, 0], [100, 100], &settings, open_except(&[])).map(|path| path.len()),
+            None
+        );
+    }
+
+    #[test]
+    fn three_dimensions() {
+        let settings = PathSettings::default();
+        let path = dijkstra_by([0, 0, 0], [2, 2, 2], &settings, |_| Some(1)).unwrap();
+        assert_eq!(path, vec![[0, 0, 0], [1, 1, 1], [2, 2, 2]]);
+    }
+
+    #[test]
+    fn blocked_goal() {
+        let settings = PathSettings::default();
+        let mut checked = 0;
+        let path = astar_by([0, 0], [5, 5], &settings, |cell_c| {
+            checked += 1;
+            (cell_c != [5, 5]).then_some(1)
+        });
+        assert_eq!(path, None);
+        assert_eq!(checked, 1);
+        assert!(settings.max_visited.is_some());
+    }
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 4;
+    }
+
+    #[test]
+    fn blocked_paths_stay_in_bounds() {
+        let mut world = World::new();
+        let config = CellMapConfig::<TestLayer>::default().with_bounds([0, 0], [4, 4]);
+        let map_id = world.spawn(CellMap::new(config)).id();
+        let mut cells = world.cells_on::<TestLayer, 2>(map_id);
+        // The shortest way around the wall would leave the map below it
+        for cell_c in [[2, 0], [2, 1], [2, 2], [2, 3]] {
+            cells.spawn_cell(cell_c, ()).unwrap();
+        }
+
+        let mut state = SystemState::<CellQuery<TestLayer, ()>>::new(&mut world);
+        let blocked_q = state.get(&world);
+        let settings = PathSettings {
+            adjacency: Adjacency::Face,
+            ..Default::default()
+        };
+        let path = astar_blocked(&blocked_q, [0, 0], [4, 0], &settings).unwrap();
+        assert_eq!(path.len(), 13);
+        assert!(path.iter().all(|cell_c| blocked_q.contains(*cell_c)));
+        assert_eq!(astar_blocked(&blocked_q, [0, 0], [5, 0], &settings), None);
+    }
+}
@@ -0,0 +1,659 @@
+use std::{any::TypeId, collections::HashSet, fmt};
+
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    },
+    reflect::{
+        serde::{ReflectSerializer, UntypedReflectDeserializer},
+        Reflect, TypeRegistry,
+    },
+};
+use serde::{
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{
+    commands::{find_map, insert_cell_batch, insert_chunk_batch},
+    generation::ChunkGenerated,
+    BoundsPolicy, CellCoord, CellMap, CellMapConfig, CellMapLabel, Chunk, InsertPolicy,
+};
+
+/// Selects which reflected components are saved along with a map.
+/// # Note
+/// Components must be registered in the [AppTypeRegistry] with `#[reflect(Component)]`,
+/// anything else is skipped.
+#[derive(Default, Clone, Debug)]
+pub struct SaveFilter {
+    components: HashSet<TypeId>,
+}
+
+impl SaveFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Saves components of type `T`.
+    pub fn allow<T: Component + Reflect>(mut self) -> Self {
+        self.components.insert(TypeId::of::<T>());
+        self
+    }
+}
+
+/// A saved chunk or cell, keyed by its coordinate instead of its entity.
+#[derive(Debug)]
+pub struct SavedEntity<const N: usize = 2> {
+    pub coord: [isize; N],
+    pub components: Vec<Box<dyn Reflect>>,
+}
+
+/// A snapshot of a cell map's chunks and cells that can be serialized and loaded back.
+#[derive(Debug)]
+pub struct CellMapSave<const N: usize = 2> {
+    pub chunks: Vec<SavedEntity<N>>,
+    pub cells: Vec<SavedEntity<N>>,
+    /// The settings of the map, `None` for saves made before settings were saved.
+    pub config: Option<SavedConfig<N>>,
+}
+
+/// The [CellMapConfig] of a saved map, without its label.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SavedConfig<const N: usize = 2> {
+    pub chunk_size: [usize; N],
+    pub insert_policy: InsertPolicy,
+    pub bounds: Option<([isize; N], [isize; N])>,
+    pub bounds_policy: BoundsPolicy,
+}
+
+impl<L, const N: usize> From<&CellMapConfig<L, N>> for SavedConfig<N>
+where
+    L: CellMapLabel + 'static,
+{
+    fn from(config: &CellMapConfig<L, N>) -> Self {
+        Self {
+            chunk_size: config.chunk_size(),
+            insert_policy: config.insert_policy(),
+            bounds: config.bounds(),
+            bounds_policy: config.bounds_policy(),
+        }
+    }
+}
+
+impl<const N: usize> SavedConfig<N> {
+    /// Gets the settings to create a map for the label with.
+    pub fn to_config<L>(&self) -> CellMapConfig<L, N>
+    where
+        L: CellMapLabel + 'static,
+    {
+        let config = CellMapConfig::default()
+            .with_chunk_size(self.chunk_size)
+            .with_insert_policy(self.insert_policy)
+            .with_bounds_policy(self.bounds_policy);
+        match self.bounds {
+            Some((min, max)) => config.with_bounds(min, max),
+            None => config,
+        }
+    }
+}
+
+impl<const N: usize> CellMapSave<N> {
+    /// Gets a serializer for this save, which needs the type registry to write components.
+    pub fn serializer<'a>(&'a self, registry: &'a TypeRegistry) -> CellMapSaveSerializer<'a, N> {
+        CellMapSaveSerializer {
+            save: self,
+            registry,
+        }
+    }
+
+    /// Gets a [DeserializeSeed] for a save, which needs the type registry to read components.
+    pub fn deserializer(registry: &TypeRegistry) -> CellMapSaveDeserializer<'_, N> {
+        CellMapSaveDeserializer { registry }
+    }
+}
+
+/// Saves the chunks and cells of a map along with the components selected by the filter.
+/// If no map entity is given, the single map for the label is used.
+/// Returns `None` if the map doesn't exist.
+pub fn save_map<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    filter: &SaveFilter,
+) -> Option<CellMapSave<N>>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let map_id = find_map::<L, N>(world, map_id)?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+
+    let reflect_components = filter
+        .components
+        .iter()
+        .filter_map(|type_id| registry.get(*type_id)?.data::<ReflectComponent>())
+        .collect::<Vec<_>>();
+    let save_components = |world: &World, id: Entity| {
+        let entity = world.entity(id);
+        reflect_components
+            .iter()
+            .filter_map(|reflect| reflect.reflect(entity))
+            .map(|component| component.clone_value())
+            .collect::<Vec<_>>()
+    };
+
+    let map = world.get::<CellMap<L, N>>(map_id)?;
+    let map_config = map.config();
+    let mut chunk_ids = map
+        .chunks
+        .iter()
+        .map(|(chunk_c, chunk_id)| (**chunk_c, *chunk_id))
+        .collect::<Vec<_>>();
+    // Keep the output stable between saves of the same map
+    chunk_ids.sort_by_key(|(chunk_c, _)| *chunk_c);
+
+    let mut chunks = Vec::with_capacity(chunk_ids.len());
+    let mut cells = Vec::new();
+    for (chunk_c, chunk_id) in chunk_ids {
+        chunks.push(SavedEntity {
+            coord: chunk_c,
+            components: save_components(world, chunk_id),
+        });
+
+        let Some(chunk) = world.get::<Chunk>(chunk_id) else {
+            continue;
+        };
+        for cell_id in chunk.cells.iter().flatten() {
+            if let Some(cell_c) = world.get::<CellCoord<N>>(*cell_id) {
+                cells.push(SavedEntity {
+                    coord: **cell_c,
+                    components: save_components(world, *cell_id),
+                });
+            }
+        }
+    }
+
+    let config = Some(SavedConfig::from(map_config));
+    Some(CellMapSave {
+        chunks,
+        cells,
+        config,
+    })
+}
+
+/// Spawns the chunks and cells of a save into a map, rebuilding their relations.
+/// If no map entity is given, the single map for the label is used, or one is spawned.
+/// Returns the map entity, or `None` if the given map entity doesn't exist.
+/// # Note
+/// Chunks and cells already in the map at saved coordinates are replaced.
+/// Saved chunks are marked as [ChunkGenerated], so a generator doesn't fill them in again.
+/// A new map is created with the saved settings. An existing map keeps its own settings, and if its
+/// chunk size differs from the save the saved chunks are skipped, since their coordinates don't line up.
+pub fn load_map<L, const N: usize>(
+    world: &mut World,
+    map_id: Option<Entity>,
+    save: &CellMapSave<N>,
+) -> Option<Entity>
+where
+    L: CellMapLabel + Send + 'static,
+{
+    let saved_config = save
+        .config
+        .map_or_else(CellMapConfig::default, |config| config.to_config::<L>());
+    let map_id = match find_map::<L, N>(world, map_id) {
+        Some(map_id) => map_id,
+        None => match map_id {
+            Some(map_id) => {
+                world
+                    .get_entity_mut(map_id)?
+                    .insert(CellMap::<L, N>::new(saved_config));
+                map_id
+            }
+            None => world.spawn(CellMap::<L, N>::new(saved_config)).id(),
+        },
+    };
+    let chunks_line_up = world
+        .get::<CellMap<L, N>>(map_id)
+        .is_some_and(|map| map.config.chunk_size() == saved_config.chunk_size());
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let spawn_saved = |world: &mut World, saved: &SavedEntity<N>| {
+        let mut entity = world.spawn_empty();
+        for component in saved.components.iter() {
+            // Deserialized components are dynamic, so look them up by the type they represent
+            if let Some(reflect) = component
+                .get_represented_type_info()
+                .and_then(|info| registry.get(info.type_id()))
+                .and_then(|registration| registration.data::<ReflectComponent>())
+            {
+                reflect.insert(&mut entity, component.as_reflect());
+            }
+        }
+        (saved.coord, entity.id())
+    };
+
+    if chunks_line_up {
+        let chunks = save
+            .chunks
+            .iter()
+            .map(|saved| spawn_saved(world, saved))
+            .collect::<Vec<_>>();
+        for (_, chunk_id) in chunks.iter() {
+            world.entity_mut(*chunk_id).insert(ChunkGenerated);
+        }
+        insert_chunk_batch::<L, N>(world, Some(map_id), chunks).ok()?;
+    }
+
+    let cells = save
+        .cells
+        .iter()
+        .map(|saved| spawn_saved(world, saved))
+        .collect::<Vec<_>>();
+    insert_cell_batch::<L, N>(world, Some(map_id), cells);
+
+    Some(map_id)
+}
+
+// =============
+// Serialization
+// =============
+
+/// Serializes a [CellMapSave] using the type registry.
+pub struct CellMapSaveSerializer<'a, const N: usize> {
+    save: &'a CellMapSave<N>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, const N: usize> Serialize for CellMapSaveSerializer<'a, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("CellMapSave", 3)?;
+        state.serialize_field(
+            "chunks",
+            &SavedEntitiesSerializer {
+                entities: &self.save.chunks,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            "cells",
+            &SavedEntitiesSerializer {
+                entities: &self.save.cells,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field("config", &self.save.config.map(SavedConfigRepr::from))?;
+        state.end()
+    }
+}
+
+struct SavedEntitiesSerializer<'a, const N: usize> {
+    entities: &'a [SavedEntity<N>],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, const N: usize> Serialize for SavedEntitiesSerializer<'a, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.entities.len()))?;
+        for saved in self.entities {
+            seq.serialize_element(&SavedEntitySerializer {
+                saved,
+                registry: self.registry,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+struct SavedEntitySerializer<'a, const N: usize> {
+    saved: &'a SavedEntity<N>,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, const N: usize> Serialize for SavedEntitySerializer<'a, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("SavedEntity", 2)?;
+        // serde only implements arrays up to a fixed size, so write coordinates as a sequence
+        state.serialize_field("coord", self.saved.coord.as_slice())?;
+        state.serialize_field(
+            "components",
+            &ComponentsSerializer {
+                components: &self.saved.components,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct ComponentsSerializer<'a> {
+    components: &'a [Box<dyn Reflect>],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for ComponentsSerializer<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.components.len()))?;
+        for component in self.components {
+            seq.serialize_element(&ReflectSerializer::new(
+                component.as_reflect(),
+                self.registry,
+            ))?;
+        }
+        seq.end()
+    }
+}
+
+// ===============
+// Deserialization
+// ===============
+
+const SAVE_FIELDS: &[&str] = &["chunks", "cells", "config"];
+const SAVED_ENTITY_FIELDS: &[&str] = &["coord", "components"];
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SaveField {
+    Chunks,
+    Cells,
+    Config,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SavedEntityField {
+    Coord,
+    Components,
+}
+
+/// Deserializes a [CellMapSave] using the type registry.
+pub struct CellMapSaveDeserializer<'a, const N: usize> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de, const N: usize> DeserializeSeed<'de> for CellMapSaveDeserializer<'a, N> {
+    type Value = CellMapSave<N>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("CellMapSave", SAVE_FIELDS, self)
+    }
+}
+
+impl<'a, 'de, const N: usize> Visitor<'de> for CellMapSaveDeserializer<'a, N> {
+    type Value = CellMapSave<N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a cell map save")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let chunks = seq
+            .next_element_seed(SavedEntitiesDeserializer::<N>(self.registry))?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let cells = seq
+            .next_element_seed(SavedEntitiesDeserializer::<N>(self.registry))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let config = seq
+            .next_element::<Option<SavedConfigRepr>>()?
+            .flatten()
+            .map(SavedConfigRepr::into_config)
+            .transpose()?;
+        Ok(CellMapSave {
+            chunks,
+            cells,
+            config,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut chunks = None;
+        let mut cells = None;
+        let mut config = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SaveField::Chunks => {
+                    chunks = Some(map.next_value_seed(SavedEntitiesDeserializer(self.registry))?)
+                }
+                SaveField::Cells => {
+                    cells = Some(map.next_value_seed(SavedEntitiesDeserializer(self.registry))?)
+                }
+                SaveField::Config => config = map.next_value::<Option<SavedConfigRepr>>()?,
+            }
+        }
+        Ok(CellMapSave {
+            chunks: chunks.ok_or_else(|| de::Error::missing_field("chunks"))?,
+            cells: cells.ok_or_else(|| de::Error::missing_field("cells"))?,
+            config: config.map(SavedConfigRepr::into_config).transpose()?,
+        })
+    }
+}
+
+/// How a [SavedConfig] is written, since serde only implements arrays up to a fixed size.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "SavedConfig")]
+struct SavedConfigRepr {
+    chunk_size: Vec<usize>,
+    insert_policy: InsertPolicy,
+    bounds: Option<(Vec<isize>, Vec<isize>)>,
+    bounds_policy: BoundsPolicy,
+}
+
+impl<const N: usize> From<SavedConfig<N>> for SavedConfigRepr {
+    fn from(config: SavedConfig<N>) -> Self {
+        Self {
+            chunk_size: config.chunk_size.to_vec(),
+            insert_policy: config.insert_policy,
+            bounds: config.bounds.map(|(min, max)| (min.to_vec(), max.to_vec())),
+            bounds_policy: config.bounds_policy,
+        }
+    }
+}
+
+impl SavedConfigRepr {
+    fn into_config<E: de::Error, const N: usize>(self) -> Result<SavedConfig<N>, E> {
+        let chunk_size: [usize; N] = axis_array(self.chunk_size)?;
+        if chunk_size.contains(&0) {
+            return Err(E::invalid_value(
+                de::Unexpected::Unsigned(0),
+                &"a chunk size of at least 1",
+            ));
+        }
+        let bounds = match self.bounds {
+            Some((min, max)) => Some((axis_array(min)?, axis_array(max)?)),
+            None => None,
+        };
+        Ok(SavedConfig {
+            chunk_size,
+            insert_policy: self.insert_policy,
+            bounds,
+            bounds_policy: self.bounds_policy,
+        })
+    }
+}
+
+/// Turns a saved sequence back into an array with one value per axis.
+fn axis_array<T, E: de::Error, const N: usize>(values: Vec<T>) -> Result<[T; N], E> {
+    let len = values.len();
+    values
+        .try_into()
+        .map_err(|_| E::invalid_length(len, &format!("{N} axes").as_str()))
+}
+
+struct SavedEntitiesDeserializer<'a, const N: usize>(&'a TypeRegistry);
+
+impl<'a, 'de, const N: usize> DeserializeSeed<'de> for SavedEntitiesDeserializer<'a, N> {
+    type Value = Vec<SavedEntity<N>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de, const N: usize> Visitor<'de> for SavedEntitiesDeserializer<'a, N> {
+    type Value = Vec<SavedEntity<N>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of saved entities")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut entities = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(saved) = seq.next_element_seed(SavedEntityDeserializer(self.0))? {
+            entities.push(saved);
+        }
+        Ok(entities)
+    }
+}
+
+struct SavedEntityDeserializer<'a, const N: usize>(&'a TypeRegistry);
+
+impl<'a, 'de, const N: usize> DeserializeSeed<'de> for SavedEntityDeserializer<'a, N> {
+    type Value = SavedEntity<N>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("SavedEntity", SAVED_ENTITY_FIELDS, self)
+    }
+}
+
+impl<'a, 'de, const N: usize> Visitor<'de> for SavedEntityDeserializer<'a, N> {
+    type Value = SavedEntity<N>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a saved chunk or cell")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let coord = seq
+            .next_element::<Vec<isize>>()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let components = seq
+            .next_element_seed(ComponentsDeserializer(self.0))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(SavedEntity {
+            coord: axis_array(coord)?,
+            components,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut coord = None;
+        let mut components = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SavedEntityField::Coord => coord = Some(map.next_value::<Vec<isize>>()?),
+                SavedEntityField::Components => {
+                    components = Some(map.next_value_seed(ComponentsDeserializer(self.0))?)
+                }
+            }
+        }
+        Ok(SavedEntity {
+            coord: axis_array(coord.ok_or_else(|| de::Error::missing_field("coord"))?)?,
+            components: components.ok_or_else(|| de::Error::missing_field("components"))?,
+        })
+    }
+}
+
+struct ComponentsDeserializer<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for ComponentsDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for ComponentsDeserializer<'a> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of reflected components")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut components = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(component) =
+            seq.next_element_seed(UntypedReflectDeserializer::new(self.0))?
+        {
+            components.push(component);
+        }
+        Ok(components)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{app::App, ecs::reflect::ReflectComponent, reflect::Reflect};
+    use serde::de::DeserializeSeed;
+
+    use super::*;
+    use crate::{cells::commands::insert_cell, CellsPlugin};
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 16;
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Unsaved;
+
+    #[test]
+    fn round_trip_ron() {
+        let mut app = App::new();
+        app.add_plugins(CellsPlugin).register_type::<Health>();
+        let world = &mut app.world;
+
+        let config = CellMapConfig::<TestLayer>::default()
+            .with_chunk_size([8, 4])
+            .with_bounds([-32, -32], [31, 31]);
+        world.spawn(CellMap::new(config));
+        let cell_1 = world.spawn((Health(3), Unsaved)).id();
+        let cell_2 = world.spawn(Health(7)).id();
+        insert_cell::<TestLayer, 2>(world, None, [0, 0], cell_1).unwrap();
+        insert_cell::<TestLayer, 2>(world, None, [-20, 5], cell_2).unwrap();
+
+        let filter = SaveFilter::new().allow::<Health>();
+        let save = save_map::<TestLayer, 2>(world, None, &filter).unwrap();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let text = ron::to_string(&save.serializer(&registry)).unwrap();
+
+        let mut deserializer = ron::Deserializer::from_str(&text).unwrap();
+        let loaded = CellMapSave::<2>::deserializer(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(loaded.chunks.len(), 2);
+        assert_eq!(loaded.cells.len(), 2);
+        assert_eq!(loaded.config, save.config);
+
+        let mut fresh = App::new();
+        fresh.add_plugins(CellsPlugin).register_type::<Health>();
+        let world = &mut fresh.world;
+        let map_id = load_map::<TestLayer, 2>(world, None, &loaded).unwrap();
+
+        let map = world.get::<CellMap<TestLayer, 2>>(map_id).unwrap();
+        assert_eq!(map.chunks.len(), 2);
+        assert!(map.chunks.contains_key(&[-3, 1].into()));
+        assert_eq!(map.config().chunk_size(), [8, 4]);
+        assert_eq!(map.config().bounds(), Some(([-32, -32], [31, 31])));
+        let mut cells = world
+            .query::<(&CellCoord<2>, &Health)>()
+            .iter(world)
+            .map(|(cell_c, health)| (**cell_c, health.0))
+            .collect::<Vec<_>>();
+        cells.sort();
+        assert_eq!(cells, vec![([-20, 5], 7), ([0, 0], 3)]);
+        assert!(world.query::<&Unsaved>().iter(world).next().is_none());
+
+        world.despawn(map_id);
+        assert_eq!(load_map::<TestLayer, 2>(world, Some(map_id), &loaded), None);
+    }
+}
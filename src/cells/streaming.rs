@@ -0,0 +1,366 @@
+use std::{collections::HashSet, marker::PhantomData};
+
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventWriter},
+        system::{Commands, Query, ResMut, Resource},
+    },
+};
+
+use super::{
+    commands::CellCommandExt,
+    coords::{calculate_cell_index, calculate_chunk_coordinate, CoordIterator},
+    CellCoord, CellMap, CellMapConfig, CellMapLabel, Chunk,
+};
+
+/// Streams chunks in and out of the maps for a label based on the [ChunkAnchor]s in the world.
+/// # Note
+/// Chunks within an anchor's radius that aren't in its map are requested with a [ChunkRequested] event.
+/// Chunks further than the radius plus the unload margin from every anchor of their map are despawned,
+/// including chunks that weren't streamed in. Maps without any anchors are left alone.
+pub struct ChunkStreamingPlugin<L, const N: usize = 2> {
+    /// How many chunks past an anchor's radius a chunk is kept before it's unloaded.
+    /// Keeps chunks from thrashing when an anchor moves back and forth over a chunk boundary.
+    pub unload_margin: usize,
+    label: PhantomData<L>,
+}
+
+impl<L, const N: usize> ChunkStreamingPlugin<L, N> {
+    pub fn new(unload_margin: usize) -> Self {
+        Self {
+            unload_margin,
+            label: PhantomData,
+        }
+    }
+}
+
+impl<L, const N: usize> Default for ChunkStreamingPlugin<L, N> {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl<L, const N: usize> Plugin for ChunkStreamingPlugin<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ChunkStreaming::<L, N> {
+            unload_margin: self.unload_margin,
+            requested: HashSet::new(),
+            label: PhantomData,
+        })
+        .add_event::<ChunkRequested<L, N>>()
+        .add_systems(Update, stream_chunks::<L, N>);
+    }
+}
+
+/// Keeps chunks of a map for a label loaded around this entity.
+#[derive(Component, Debug)]
+pub struct ChunkAnchor<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    /// The cell the anchor is on.
+    /// # Note
+    /// If the anchor entity is a cell, its [CellCoord] is used instead so the two can't drift apart.
+    pub cell_c: [isize; N],
+    /// How many chunks around the anchor's chunk are loaded.
+    pub radius: usize,
+    /// The map the anchor streams, `None` streams the single map for the label.
+    /// # Note
+    /// If the anchor entity is a cell, the map it's in is used instead.
+    pub map_id: Option<Entity>,
+    pub label: PhantomData<L>,
+}
+
+impl<L, const N: usize> ChunkAnchor<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    pub fn new(cell_c: [isize; N], radius: usize) -> Self {
+        Self {
+            cell_c,
+            radius,
+            map_id: None,
+            label: PhantomData,
+        }
+    }
+
+    /// Streams the given map instead of the single map for the label.
+    pub fn with_map(mut self, map_id: Entity) -> Self {
+        self.map_id = Some(map_id);
+        self
+    }
+
+    /// Gets the coordinate of the chunk the anchor is on, in a map with the given settings.
+    pub fn chunk_c(&self, config: &CellMapConfig<L, N>) -> [isize; N] {
+        calculate_chunk_coordinate(self.cell_c, config.chunk_size())
+    }
+}
+
+/// Sent when a chunk comes into range of an anchor and isn't in the map.
+/// # Note
+/// A chunk is only requested once while it stays in range, spawn it with
+/// [spawn_chunk](super::commands::CellCommands::spawn_chunk) or any cell inside it to load it.
+#[derive(Event, Debug)]
+pub struct ChunkRequested<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    /// The map missing the chunk, `None` if the label has no map yet.
+    pub map_id: Option<Entity>,
+    pub chunk_c: [isize; N],
+    pub label: PhantomData<L>,
+}
+
+/// Tracks the chunks that have been requested for a label.
+#[derive(Resource)]
+pub struct ChunkStreaming<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    pub unload_margin: usize,
+    requested: HashSet<(Option<Entity>, [isize; N])>,
+    label: PhantomData<L>,
+}
+
+impl<L, const N: usize> ChunkStreaming<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    /// Returns true if the chunk has been requested for any map and hasn't been loaded yet.
+    pub fn is_requested(&self, chunk_c: [isize; N]) -> bool {
+        self.requested.iter().any(|(_, c)| *c == chunk_c)
+    }
+
+    /// Returns true if the chunk has been requested for the map and hasn't been loaded yet.
+    /// # Note
+    /// `None` is the map that will be spawned for the label if it doesn't have one yet.
+    pub fn is_requested_on(&self, map_id: Option<Entity>, chunk_c: [isize; N]) -> bool {
+        self.requested.contains(&(map_id, chunk_c))
+    }
+}
+
+/// Gets the chebyshev distance between two chunks.
+#[inline]
+fn chunk_distance<const N: usize>(chunk_c_1: [isize; N], chunk_c_2: [isize; N]) -> usize {
+    chunk_c_1
+        .iter()
+        .zip(chunk_c_2)
+        .map(|(c1, c2)| c1.abs_diff(c2))
+        .max()
+        .unwrap_or_default()
+}
+
+pub(crate) fn stream_chunks<L, const N: usize>(
+    mut commands: Commands,
+    mut streaming: ResMut<ChunkStreaming<L, N>>,
+    mut requests: EventWriter<ChunkRequested<L, N>>,
+    anchors: Query<(Entity, &ChunkAnchor<L, N>, Option<&CellCoord<N>>)>,
+    maps: Query<(Entity, &CellMap<L, N>)>,
+    chunks: Query<&Chunk>,
+) where
+    L: CellMapLabel + 'static,
+{
+    // Anchors that are cells follow their coordinate in the map they're in
+    let anchors = anchors
+        .iter()
+        .map(|(anchor_id, anchor, cell_c)| match cell_c {
+            Some(cell_c) => {
+                let map_id = maps
+                    .iter()
+                    .find(|(_, map)| holds_cell(map, &chunks, **cell_c, anchor_id))
+                    .map(|(map_id, _)| map_id);
+                (map_id.or(anchor.map_id), **cell_c, anchor.radius)
+            }
+            None => (anchor.map_id, anchor.cell_c, anchor.radius),
+        })
+        .collect::<Vec<_>>();
+    let unload_margin = streaming.unload_margin;
+    let single_map = maps.iter().len() == 1;
+
+    // Until the label has a map, chunks are requested for the map that will be spawned for them
+    let mut maps = maps
+        .iter()
+        .map(|(map_id, map)| (Some(map_id), Some(map)))
+        .collect::<Vec<_>>();
+    if maps.is_empty() {
+        maps.push((None, None));
+    }
+    streaming
+        .requested
+        .retain(|(map_id, _)| maps.iter().any(|(id, _)| id == map_id));
+
+    for (map_id, map) in maps {
+        let is_loaded = |chunk_c: &[isize; N]| {
+            map.is_some_and(|map| map.chunks.contains_key(&(*chunk_c).into()))
+        };
+        let chunk_size = map.map_or_else(L::chunk_size, |map| map.config.chunk_size());
+        let anchor_cs = anchors
+            .iter()
+            .filter(|(anchor_map_id, _, _)| {
+                *anchor_map_id == map_id || (anchor_map_id.is_none() && single_map)
+            })
+            .map(|(_, cell_c, radius)| (calculate_chunk_coordinate(*cell_c, chunk_size), *radius))
+            .collect::<Vec<_>>();
+        let in_range = |chunk_c: [isize; N], margin: usize| {
+            anchor_cs
+                .iter()
+                .any(|(anchor_c, radius)| chunk_distance(chunk_c, *anchor_c) <= radius + margin)
+        };
+
+        // Forget requests that were loaded or have gone out of range
+        streaming.requested.retain(|(id, chunk_c)| {
+            *id != map_id || (!is_loaded(chunk_c) && in_range(*chunk_c, unload_margin))
+        });
+
+        for (anchor_c, radius) in anchor_cs.iter() {
+            let radius = *radius as isize;
+            let corner_1 = anchor_c.map(|c| c - radius);
+            let corner_2 = anchor_c.map(|c| c + radius);
+            for chunk_c in CoordIterator::new(corner_1, corner_2) {
+                if !is_loaded(&chunk_c) && streaming.requested.insert((map_id, chunk_c)) {
+                    requests.send(ChunkRequested {
+                        map_id,
+                        chunk_c,
+                        label: PhantomData,
+                    });
+                }
+            }
+        }
+
+        // Maps nothing is anchored to, like hand built maps or maps waiting on a player, are kept
+        if anchor_cs.is_empty() {
+            continue;
+        }
+        if let (Some(map_id), Some(map)) = (map_id, map) {
+            let unloaded = map
+                .chunks
+                .keys()
+                .map(|chunk_c| **chunk_c)
+                .filter(|chunk_c| !in_range(*chunk_c, unload_margin))
+                .collect::<Vec<_>>();
+            if !unloaded.is_empty() {
+                commands
+                    .cells_on::<L, N>(map_id)
+                    .despawn_chunk_batch(unloaded);
+            }
+        }
+    }
+}
+
+/// Returns true if the cell is in the map at the given coordinate.
+#[inline]
+fn holds_cell<L, const N: usize>(
+    map: &CellMap<L, N>,
+    chunks: &Query<&Chunk>,
+    cell_c: [isize; N],
+    cell_id: Entity,
+) -> bool
+where
+    L: CellMapLabel + 'static,
+{
+    let chunk_size = map.config.chunk_size();
+    map.chunks
+        .get(&calculate_chunk_coordinate(cell_c, chunk_size).into())
+        .and_then(|chunk_id| chunks.get(*chunk_id).ok())
+        .and_then(|chunk| chunk.cells.get(calculate_cell_index(cell_c, chunk_size)))
+        == Some(&Some(cell_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::event::{Events, ManualEventReader};
+
+    use super::*;
+    use crate::{
+        cells::commands::{insert_cell, insert_chunk},
+        CellsPlugin,
+    };
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 16;
+    }
+
+    #[test]
+    fn requests_and_unloads_with_margin() {
+        let mut app = App::new();
+        app.add_plugins((CellsPlugin, ChunkStreamingPlugin::<TestLayer>::new(1)));
+        let anchor = app
+            .world
+            .spawn(ChunkAnchor::<TestLayer>::new([0, 0], 1))
+            .id();
+
+        let mut reader = ManualEventReader::<ChunkRequested<TestLayer>>::default();
+        app.update();
+        let requested = reader
+            .read(app.world.resource::<Events<ChunkRequested<TestLayer>>>())
+            .count();
+        assert_eq!(requested, 9);
+
+        // Nothing is re-requested while the chunks stay in range
+        app.update();
+        let requested = reader
+            .read(app.world.resource::<Events<ChunkRequested<TestLayer>>>())
+            .count();
+        assert_eq!(requested, 0);
+
+        let chunk = app.world.spawn_empty().id();
+        insert_chunk::<TestLayer, 2>(&mut app.world, None, [-1, 0], chunk).unwrap();
+
+        // Two chunks away is still within the margin
+        app.world
+            .get_mut::<ChunkAnchor<TestLayer>>(anchor)
+            .unwrap()
+            .cell_c = [16, 0];
+        app.update();
+        assert!(app.world.get_entity(chunk).is_some());
+
+        app.world
+            .get_mut::<ChunkAnchor<TestLayer>>(anchor)
+            .unwrap()
+            .cell_c = [32, 0];
+        app.update();
+        assert!(app.world.get_entity(chunk).is_none());
+    }
+
+    #[test]
+    fn streams_maps_around_their_anchors() {
+        let mut app = App::new();
+        app.add_plugins((CellsPlugin, ChunkStreamingPlugin::<TestLayer>::new(0)));
+        let map_1 = app.world.spawn(CellMap::<TestLayer>::default()).id();
+        let map_2 = app.world.spawn(CellMap::<TestLayer>::default()).id();
+        let map_3 = app.world.spawn(CellMap::<TestLayer>::default()).id();
+        let far = app.world.spawn_empty().id();
+        insert_chunk::<TestLayer, 2>(&mut app.world, Some(map_2), [-5, 5], far).unwrap();
+        let built = app.world.spawn_empty().id();
+        insert_chunk::<TestLayer, 2>(&mut app.world, Some(map_3), [-5, 5], built).unwrap();
+
+        // The anchor is a cell, so it streams around its coordinate in its own map
+        // and not the stale one it was given
+        let cell_anchor = app
+            .world
+            .spawn(ChunkAnchor::<TestLayer>::new([0, 0], 0).with_map(map_2))
+            .id();
+        insert_cell::<TestLayer, 2>(&mut app.world, Some(map_1), [40, 0], cell_anchor).unwrap();
+        app.world
+            .spawn(ChunkAnchor::<TestLayer>::new([0, 0], 0).with_map(map_2));
+        app.update();
+
+        let requested = ManualEventReader::<ChunkRequested<TestLayer>>::default()
+            .read(app.world.resource::<Events<ChunkRequested<TestLayer>>>())
+            .map(|request| (request.map_id, request.chunk_c))
+            .collect::<Vec<_>>();
+        assert_eq!(requested, vec![(Some(map_2), [0, 0])]);
+        assert!(app.world.get_entity(cell_anchor).is_some());
+        assert!(app.world.get_entity(far).is_none());
+        // Nothing is anchored to the third map, so its chunks stay
+        assert!(app.world.get_entity(built).is_some());
+    }
+}
@@ -0,0 +1,301 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    ecs::{
+        change_detection::DetectChanges,
+        query::Changed,
+        schedule::IntoSystemConfigs,
+        system::{ParamSet, Res, Resource},
+    },
+    math::{Quat, Vec3},
+    transform::{components::Transform, TransformSystem},
+};
+
+use super::{
+    cell_query::CellQuery,
+    coords::hex::{hex_to_world, world_to_hex, Hex, HexOrientation},
+    CellCoord, CellMapLabel,
+};
+
+/// Keeps the [Transform] of cells in sync with their [CellCoord] using the [GridGeometry] for the label.
+/// # Note
+/// In maps with less than 3 dimensions, the `z` translation of cells is left as is.
+pub struct CellTransformPlugin<L, const N: usize = 2>(PhantomData<L>);
+
+impl<L, const N: usize> Default for CellTransformPlugin<L, N> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<L, const N: usize> Plugin for CellTransformPlugin<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GridGeometry<L, N>>().add_systems(
+            PostUpdate,
+            sync_cell_transforms::<L, N>.before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+/// How cell coordinates are laid out in the world.
+/// # Note
+/// [Hex](GridProjection::Hex) and [Isometric](GridProjection::Isometric) lay out the first two axes,
+/// so they need maps with at least 2 dimensions.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum GridProjection {
+    /// Cells are laid out along the world axes.
+    #[default]
+    Square,
+    /// The first two axes are axial [Hex] coordinates, the cell size on the first axis
+    /// is the size (center to corner) of a hex.
+    Hex(HexOrientation),
+    /// The first two axes are laid out as a diamond, the cell size is the width and height of a tile.
+    Isometric,
+}
+
+/// Describes how cell coordinates in a map map to world coordinates.
+/// # Note
+/// Along square axes, cell `c` covers `c * size` inclusive to `(c + 1) * size` exclusive from the origin,
+/// the same as [world_to_cell](super::coords::world_to_cell) and [GridRay](super::coords::GridRay).
+/// Hex and isometric cells are centered on their projected position instead.
+/// Axes past the projected ones are laid out along the world `z` axis.
+#[derive(Resource, Debug)]
+pub struct GridGeometry<L, const N: usize = 2>
+where
+    L: CellMapLabel + 'static,
+{
+    /// The size of a cell along each axis.
+    pub cell_size: [f32; N],
+    /// The world position of the corner of the cell at the origin, or its center for hex and isometric cells.
+    pub origin: Vec3,
+    /// The rotation of the grid around the origin.
+    pub rotation: Quat,
+    pub projection: GridProjection,
+    pub label: PhantomData<L>,
+}
+
+impl<L, const N: usize> Default for GridGeometry<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    fn default() -> Self {
+        Self::new([1.0; N])
+    }
+}
+
+impl<L, const N: usize> GridGeometry<L, N>
+where
+    L: CellMapLabel + 'static,
+{
+    pub fn new(cell_size: [f32; N]) -> Self {
+        Self {
+            cell_size,
+            origin: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            projection: GridProjection::Square,
+            label: PhantomData,
+        }
+    }
+
+    pub fn with_origin(mut self, origin: Vec3) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: Quat) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// # Panics
+    /// If the projection is hex or isometric and the map has less than 2 dimensions.
+    pub fn with_projection(mut self, projection: GridProjection) -> Self {
+        assert!(
+            N >= 2 || projection == GridProjection::Square,
+            "{projection:?} projection needs at least 2 dimensions"
+        );
+        self.projection = projection;
+        self
+    }
+
+    /// Calculate the world position of the center of a cell.
+    pub fn cell_to_world(&self, cell_c: [isize; N]) -> Vec3 {
+        let mut local = Vec3::ZERO;
+        let projected = match self.projection {
+            GridProjection::Square => 0,
+            GridProjection::Hex(orientation) => {
+                let [x, y] = hex_to_world(
+                    Hex::new(cell_c[0], cell_c[1]),
+                    self.cell_size[0],
+                    orientation,
+                );
+                local.x = x;
+                local.y = y;
+                2
+            }
+            GridProjection::Isometric => {
+                let (c0, c1) = (cell_c[0] as f32, cell_c[1] as f32);
+                local.x = (c0 - c1) * self.cell_size[0] / 2.0;
+                local.y = -(c0 + c1) * self.cell_size[1] / 2.0;
+                2
+            }
+        };
+        for (i, (c, size)) in cell_c
+            .iter()
+            .zip(self.cell_size)
+            .enumerate()
+            .skip(projected)
+        {
+            // Square axes map straight to x, y and z, otherwise the rest go along z
+            let axis = if projected == 0 { i.min(2) } else { 2 };
+            local[axis] += (*c as f32 + 0.5) * size;
+        }
+        self.origin + self.rotation * local
+    }
+
+    /// Calculate the cell containing a world position, for example to pick cells with the cursor.
+    pub fn world_to_cell(&self, world_c: Vec3) -> [isize; N] {
+        let local = self.rotation.inverse() * (world_c - self.origin);
+        let mut cell_c = [0; N];
+        let projected = match self.projection {
+            GridProjection::Square => 0,
+            GridProjection::Hex(orientation) => {
+                let hex = world_to_hex([local.x, local.y], self.cell_size[0], orientation);
+                cell_c[0] = hex.q;
+                cell_c[1] = hex.r;
+                2
+            }
+            GridProjection::Isometric => {
+                let a = local.x / (self.cell_size[0] / 2.0);
+                let b = -local.y / (self.cell_size[1] / 2.0);
+                cell_c[0] = ((a + b) / 2.0).round() as isize;
+                cell_c[1] = ((b - a) / 2.0).round() as isize;
+                2
+            }
+        };
+        if projected == 0 {
+            for (i, c) in cell_c.iter_mut().enumerate().take(3) {
+                *c = (local[i] / self.cell_size[i]).floor() as isize;
+            }
+        } else if N > projected {
+            // Only the first axis past the projection can be recovered from z
+            cell_c[projected] = (local.z / self.cell_size[projected]).floor() as isize;
+        }
+        cell_c
+    }
+}
+
+type CellTransformQuery<'w, 's, L, F, const N: usize> =
+    CellQuery<'w, 's, L, (&'static CellCoord<N>, &'static mut Transform), F, N>;
+
+fn sync_cell_transforms<L, const N: usize>(
+    geometry: Res<GridGeometry<L, N>>,
+    mut cells: ParamSet<(
+        CellTransformQuery<L, Changed<CellCoord<N>>, N>,
+        CellTransformQuery<L, (), N>,
+    )>,
+) where
+    L: CellMapLabel + 'static,
+{
+    let sync = |cell_c: &CellCoord<N>, transform: &mut Transform| {
+        let z = transform.translation.z;
+        transform.translation = geometry.cell_to_world(**cell_c);
+        // Leave the z axis alone in 2d so it can still be used for layering
+        if N < 3 {
+            transform.translation.z = z;
+        }
+    };
+
+    // Every cell needs to be moved if the geometry changes
+    if geometry.is_changed() {
+        for (cell_c, mut transform) in cells.p1().iter_mut() {
+            sync(cell_c, &mut transform);
+        }
+    } else {
+        for (cell_c, mut transform) in cells.p0().iter_mut() {
+            sync(cell_c, &mut transform);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::App;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        cells::{commands::CellWorldExt, coords::world_to_cell},
+        CellsPlugin,
+    };
+
+    struct TestLayer;
+
+    impl CellMapLabel for TestLayer {
+        const CHUNK_SIZE: usize = 16;
+    }
+
+    #[rstest]
+    #[case(GridProjection::Square)]
+    #[case(GridProjection::Hex(HexOrientation::Flat))]
+    #[case(GridProjection::Isometric)]
+    fn world_round_trip(#[case] projection: GridProjection) {
+        let geometry = GridGeometry::<TestLayer, 3>::new([16.0, 8.0, 4.0])
+            .with_origin(Vec3::new(5.0, -3.0, 1.0))
+            .with_rotation(Quat::from_rotation_z(0.5))
+            .with_projection(projection);
+        for cell_c in [[0, 0, 0], [3, -2, 1], [-7, 4, -2]] {
+            let world_c = geometry.cell_to_world(cell_c);
+            assert_eq!(geometry.world_to_cell(world_c), cell_c);
+        }
+    }
+
+    #[test]
+    fn square_cells_match_floor_coords() {
+        let geometry = GridGeometry::<TestLayer, 2>::new([16.0, 16.0]);
+        assert_eq!(geometry.cell_to_world([0, -1]), Vec3::new(8.0, -8.0, 0.0));
+        for world_c in [[0.0, 0.0], [15.9, -0.1], [-16.0, 31.0], [-0.5, 40.0]] {
+            assert_eq!(
+                geometry.world_to_cell(Vec3::new(world_c[0], world_c[1], 0.0)),
+                world_to_cell(world_c, 16.0)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn projections_need_two_axes() {
+        GridGeometry::<TestLayer, 1>::new([16.0]).with_projection(GridProjection::Isometric);
+    }
+
+    #[test]
+    fn syncs_cell_transforms() {
+        let mut app = App::new();
+        app.add_plugins((CellsPlugin, CellTransformPlugin::<TestLayer>::default()))
+            .insert_resource(GridGeometry::<TestLayer>::new([16.0, 8.0]));
+        let cell = app
+            .world
+            .cells::<TestLayer, 2>()
+            .spawn_cell([2, -1], Transform::from_xyz(0.0, 0.0, 3.0))
+            .unwrap();
+        app.update();
+        let translation = |app: &App| app.world.get::<Transform>(cell).unwrap().translation;
+        assert_eq!(translation(&app), Vec3::new(40.0, -4.0, 3.0));
+
+        app.world
+            .cells::<TestLayer, 2>()
+            .move_cell([2, -1], [0, 0])
+            .unwrap();
+        app.update();
+        assert_eq!(translation(&app), Vec3::new(8.0, 4.0, 3.0));
+
+        // Changing the geometry moves every cell, even ones that didn't move
+        app.world.resource_mut::<GridGeometry<TestLayer>>().origin = Vec3::new(100.0, 0.0, 0.0);
+        app.update();
+        assert_eq!(translation(&app), Vec3::new(108.0, 4.0, 3.0));
+    }
+}
@@ -4,11 +4,25 @@ use bevy::app::Plugin;
 pub mod cells;
 
 pub mod prelude {
+    pub use crate::cells::automaton::*;
     pub use crate::cells::cell_query::*;
-    pub use crate::cells::commands::{CellCommandExt, CellCommands};
+    pub use crate::cells::cleanup::*;
+    pub use crate::cells::commands::{
+        CellCommandExt, CellCommands, CellWorld, CellWorldExt, InsertOutcome,
+    };
     pub use crate::cells::CellMapLabel;
 
+    pub use crate::cells::coords::hex::*;
     pub use crate::cells::coords::*;
+    pub use crate::cells::data::*;
+    pub use crate::cells::error::*;
+    pub use crate::cells::events::*;
+    pub use crate::cells::fov::*;
+    pub use crate::cells::generation::*;
+    pub use crate::cells::pathfinding::*;
+    pub use crate::cells::save::*;
+    pub use crate::cells::streaming::*;
+    pub use crate::cells::transform::*;
     pub use crate::cells::*;
     pub use crate::CellsPlugin;
 }